@@ -0,0 +1,52 @@
+use std::io::{self, Write};
+use std::time::Duration;
+
+use crate::cell_shape::CellShape;
+use crate::solver::{self, Placement, SearchEvent};
+
+// Clears the terminal and moves the cursor back to the top left
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Redraws the board to `out` after every step of the search, waiting between frames.
+/// An `fps` of zero draws as fast as possible.
+pub fn animate(board: &CellShape, fps: u32, out: &mut impl Write) -> io::Result<Option<Vec<Placement>>> {
+    let delay = match fps {
+        0 => Duration::ZERO,
+        fps => Duration::from_secs(1) / fps,
+    };
+    let mut placed = Vec::new();
+    let mut result = Ok(());
+
+    let solution = solver::solve_region_traced(board, |event| {
+        match event {
+            SearchEvent::Place(p) => placed.push(p),
+            SearchEvent::Remove(p) => placed.retain(|&q| q != p),
+        }
+        if result.is_err() {
+            return;
+        }
+        result = write!(out, "{CLEAR_SCREEN}{}", solver::render_solution(board, &placed))
+            .and_then(|_| out.flush());
+        std::thread::sleep(delay);
+    });
+
+    result.map(|_| solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn animate_small_board() {
+        let board = CellShape::rectangle(5, 3);
+        let mut out = Vec::new();
+
+        let solution = animate(&board, 0, &mut out).unwrap();
+
+        assert!(solution.is_some());
+        let out = String::from_utf8(out).unwrap();
+        let last_frame = out.rsplit(CLEAR_SCREEN).next().unwrap();
+        assert!(!last_frame.contains('.'));
+    }
+}
@@ -1,4 +1,10 @@
-use crate::{coord::{Coord, Vec2D}, transform::Transform};
+use alloc::{format, string::{String, ToString}, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use crate::coord::{coords_in_rect, Coord, Vec2D};
+use crate::pentomino::{Pentomino, PENTOMINOES};
+use crate::transform::{Transform, RIGID_SYMMETRIES};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Tile {
@@ -6,6 +12,26 @@ pub enum Tile {
     Empty
 }
 
+impl Tile {
+    pub const fn is_filled(self) -> bool {
+        matches!(self, Tile::Filled)
+    }
+    pub const fn is_empty(self) -> bool {
+        matches!(self, Tile::Empty)
+    }
+    /// Returns the opposite tile, Filled becomes Empty and vice versa
+    pub const fn toggle(self) -> Tile {
+        match self {
+            Tile::Filled => Tile::Empty,
+            Tile::Empty => Tile::Filled,
+        }
+    }
+}
+
+// Default glyphs for drawing shapes
+const FILLED: char = '█';
+const EMPTY: char = ' ';
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 /// An Cell Shape, anywhere on the grid. 
 /// Different Oritentations and reflections are considered distinct, but not translations.
@@ -15,7 +41,7 @@ pub struct CellShape {
     tiles: Vec<Coord>,
 }
 
-fn coord_cmp(lhs: &Coord, rhs: &Coord) -> std::cmp::Ordering {
+fn coord_cmp(lhs: &Coord, rhs: &Coord) -> core::cmp::Ordering {
     lhs.x.cmp(&rhs.x)
         .then(lhs.y.cmp(&rhs.y))
 }
@@ -26,34 +52,32 @@ impl CellShape {
             tiles: Vec::new(),
         }
     }
+    /// A completely filled rectangle
+    pub fn rectangle(width: usize, height: usize) -> CellShape {
+        let max = Coord::new(width as isize - 1, height as isize - 1);
+        CellShape::from_coordinate_list(coords_in_rect(Coord::origin(), max).collect())
+    }
     pub fn from_2darray<const N: usize, const M: usize>(grid: [[Tile; M]; N]) -> CellShape {
+        let max = Coord::new(M as isize - 1, N as isize - 1);
+        let filled_tiles = coords_in_rect(Coord::origin(), max)
+            .filter(|c| grid[c.y as usize][c.x as usize].is_filled())
+            .collect();
+        CellShape::from_coordinate_list(filled_tiles)
+    }
+    /// Builds a shape from a grid whose size is only known at runtime, which may be ragged
+    pub fn from_grid(grid: &[Vec<Tile>]) -> CellShape {
         let mut filled_tiles = Vec::new();
-        for y in 0..N {
-            for x in 0..M {
-                let coord = Coord {
-                    x: x as isize, 
-                    y: y as isize
-                };
-                if grid[y][x] == Tile::Filled {
-                    filled_tiles.push(coord);
+        for (y, row) in grid.iter().enumerate() {
+            for (x, tile) in row.iter().enumerate() {
+                if tile.is_filled() {
+                    filled_tiles.push(Coord::new(x as isize, y as isize));
                 }
             }
         }
         CellShape::from_coordinate_list(filled_tiles)
     }
     pub fn from_coordinate_list(mut coords: Vec<Coord>) -> CellShape {
-        if coords.is_empty() {
-            return CellShape::empty();
-        }
-        let min_x = coords.iter().map(|c| c.x).min().unwrap();
-        let min_y = coords.iter().map(|c| c.y).min().unwrap();
-        coords
-            .iter_mut()
-            .for_each(|c| {
-                c.x -= min_x;
-                c.y -= min_y;
-            });
-
+        CellShape::shift_into_positive(&mut coords);
         coords.sort_by(coord_cmp);
         coords.dedup();
 
@@ -61,38 +85,518 @@ impl CellShape {
             tiles: coords
         }
     }
+    /// Like `from_coordinate_list`, also turning the shape to its `canonical` orientation,
+    /// so every orientation of the same free polyomino constructs the identical shape
+    pub fn from_coordinate_list_canonical(coords: Vec<Coord>) -> CellShape {
+        CellShape::from_coordinate_list(coords).canonical()
+    }
+    /// Unions the shapes, each moved by its offset.
+    /// Fails with the first tile, in the offset coordinates, where a shape lands on an earlier one
+    pub fn assemble(pieces: &[(CellShape, Vec2D)]) -> Result<CellShape, Coord> {
+        let mut tiles: Vec<Coord> = Vec::new();
+        for (shape, offset) in pieces {
+            for c in shape.filled_tiles() {
+                let c = c + *offset;
+                if tiles.contains(&c) {
+                    return Err(c);
+                }
+                tiles.push(c);
+            }
+        }
+        Ok(CellShape::from_coordinate_list(tiles))
+    }
+    /// Translates the coordinates so their smallest x and smallest y are at the origin,
+    /// returning the shift that was added so the caller can map them back
+    pub fn shift_into_positive(coords: &mut [Coord]) -> Vec2D {
+        let Some(min) = coords.iter().copied().reduce(Coord::component_min) else {
+            return Vec2D::zero();
+        };
+        let shift = Coord::origin() - min;
+        coords.iter_mut().for_each(|c| *c = *c + shift);
+        shift
+    }
+    /// Unpacks a shape from a row-major bitmask, with rows `width` tiles wide.
+    /// This is the inverse of `to_u64`.
+    pub fn from_u64(bits: u64, width: u8) -> CellShape {
+        if width == 0 {
+            return CellShape::empty();
+        }
+        let width = width as isize;
+        let coords = (0..64)
+            .filter(|i| bits & (1 << i) != 0)
+            .map(|i| Coord::new(i % width, i / width))
+            .collect();
+        CellShape::from_coordinate_list(coords)
+    }
+    /// Packs the tiles into a row-major bitmask over the bounding box.
+    /// Returns None if the bounding box has more than 64 tiles.
+    pub fn to_u64(&self) -> Option<u64> {
+        let (width, height) = self.bounding_box();
+        if width * height > 64 {
+            return None;
+        }
+        let bits = self.filled_tiles()
+            .fold(0, |bits, c| bits | (1 << (c.y * width + c.x)));
+        Some(bits)
+    }
+    /// The filled tiles of row `y`, with bit `x` set when tile `(x, y)` is filled.
+    /// A row outside the shape is 0, and tiles past x = 63 don't fit and are left out
+    pub fn row_mask(&self, y: isize) -> u64 {
+        self.filled_tiles()
+            .filter(|c| c.y == y && c.x < 64)
+            .fold(0, |bits, c| bits | (1 << c.x))
+    }
+    /// The filled tiles of column `x`, with bit `y` set when tile `(x, y)` is filled.
+    /// A column outside the shape is 0, and tiles past y = 63 don't fit and are left out
+    pub fn col_mask(&self, x: isize) -> u64 {
+        self.filled_tiles()
+            .filter(|c| c.x == x && c.y < 64)
+            .fold(0, |bits, c| bits | (1 << c.y))
+    }
+    /// The number of filled tiles
+    pub fn size(&self) -> usize {
+        self.tiles.len()
+    }
     /// Iterates over the filled tiles, in the local coordinate system
     pub fn filled_tiles(&self) -> impl Iterator<Item = Coord> + '_ {
         self.tiles.iter().copied()
     }
+    /// Iterates over every tile of the bounding box in row-major order, filled or not
+    pub fn cells(&self) -> impl Iterator<Item = (Coord, Tile)> + '_ {
+        let (width, height) = self.bounding_box();
+        coords_in_rect(Coord::origin(), Coord::new(width - 1, height - 1))
+            .map(|c| (c, self[c]))
+    }
+    /// Numbers the filled tiles from 0, in sorted order
+    #[cfg(feature = "std")]
+    pub fn cell_index(&self) -> HashMap<Coord, usize> {
+        self.filled_tiles()
+            .enumerate()
+            .map(|(i, c)| (c, i))
+            .collect()
+    }
+    /// The filled tile numbered `i` by `cell_index`, found at position `i`
+    pub fn index_to_cell(&self) -> Vec<Coord> {
+        self.tiles.clone()
+    }
+    /// Returns the absolute coordinates of the filled tiles, when the local origin is placed at `target_min`.
+    /// The result is not re-normalized, so it can be unioned into a larger board.
+    pub fn translate_to(&self, target_min: Coord) -> Vec<Coord> {
+        let shift = target_min - Coord::origin();
+        self.filled_tiles()
+            .map(|c| c + shift)
+            .collect()
+    }
     // Returns the maximum x and y
     pub fn max(&self) -> Coord {
-        let max_x = self.tiles.iter().map(|c| c.x).max().unwrap_or(0);
-        let max_y = self.tiles.iter().map(|c| c.y).max().unwrap_or(0);
-        Coord::new(max_x, max_y)
+        self.tiles
+            .iter()
+            .copied()
+            .fold(Coord::origin(), Coord::component_max)
+    }
+    /// Returns the minimum and maximum corners of the bounding box.
+    /// Shapes are normalized, so the minimum is always the origin.
+    pub fn extent(&self) -> (Coord, Coord) {
+        (Coord::origin(), self.max())
+    }
+    /// Mirrors the shape left to right within its own bounding box
+    pub fn flip_lr(&self) -> CellShape {
+        let max = self.max();
+        let coords = self.filled_tiles()
+            .map(|c| Coord::new(max.x - c.x, c.y))
+            .collect();
+        CellShape::from_coordinate_list(coords)
+    }
+    /// Mirrors the shape top to bottom within its own bounding box
+    pub fn flip_ud(&self) -> CellShape {
+        let max = self.max();
+        let coords = self.filled_tiles()
+            .map(|c| Coord::new(c.x, max.y - c.y))
+            .collect();
+        CellShape::from_coordinate_list(coords)
+    }
+    /// Mirrors the shape over the diagonal from the top left corner, swapping x and y.
+    /// The width and height of a non-square shape swap too.
+    pub fn flip_main_diagonal(&self) -> CellShape {
+        let coords = self.filled_tiles()
+            .map(|c| Coord::new(c.y, c.x))
+            .collect();
+        CellShape::from_coordinate_list(coords)
+    }
+    /// Mirrors the shape over the diagonal from the top right corner.
+    /// The width and height of a non-square shape swap too.
+    pub fn flip_anti_diagonal(&self) -> CellShape {
+        let max = self.max();
+        let coords = self.filled_tiles()
+            .map(|c| Coord::new(max.y - c.y, max.x - c.x))
+            .collect();
+        CellShape::from_coordinate_list(coords)
+    }
+    /// Moves every filled tile by `f`, then shifts the result back to the origin.
+    /// Tiles that `f` sends to the same coordinate merge into one
+    pub fn map_coords(&self, f: impl Fn(Coord) -> Coord) -> CellShape {
+        CellShape::from_coordinate_list(self.filled_tiles().map(f).collect())
+    }
+    /// The smallest orientation of this shape under the rigid symmetries.
+    /// Two shapes are the same free polyomino exactly when their canonical forms are equal.
+    pub fn canonical(&self) -> CellShape {
+        RIGID_SYMMETRIES
+            .iter()
+            .map(|t| t.transform_shape(self.clone()))
+            .min()
+            .unwrap()
+    }
+    /// The distinct orientations of this shape under the rigid symmetries, in RIGID_SYMMETRIES order.
+    /// Each one is built straight from this shape's tiles, and duplicates are dropped by comparing
+    /// against the earlier orientations in place, so neither this shape nor any orientation gets cloned
+    pub fn orientations_borrowed(&self) -> impl Iterator<Item = CellShape> + '_ {
+        let all: Vec<CellShape> = RIGID_SYMMETRIES
+            .iter()
+            .map(|t| self.map_coords(|c| t.transform_coord(c)))
+            .collect();
+        let distinct: Vec<bool> = (0..all.len()).map(|i| !all[..i].contains(&all[i])).collect();
+        all.into_iter()
+            .zip(distinct)
+            .filter_map(|(shape, distinct)| distinct.then_some(shape))
+    }
+    /// The orientations of this shape, keeping only the first of any that one of the board's symmetries maps onto another.
+    /// Restricting one piece to these on a symmetric board still reaches every solution up to the board's symmetry
+    pub fn orientations_modulo(&self, board_symmetries: &[Transform]) -> Vec<CellShape> {
+        let mut kept: Vec<CellShape> = Vec::new();
+        for shape in self.orientations_borrowed() {
+            let covered = kept.iter().any(|k| board_symmetries.iter().any(|t| t.transform_shape(k.clone()) == shape));
+            if !covered {
+                kept.push(shape);
+            }
+        }
+        kept
     }
+    /// A rigid symmetry taking this shape to `other`, or None if they are different free polyominoes.
+    /// When the shape is symmetric several transforms work, and the first in RIGID_SYMMETRIES is returned
+    pub fn transform_to(&self, other: &CellShape) -> Option<Transform> {
+        RIGID_SYMMETRIES
+            .iter()
+            .copied()
+            .find(|t| t.transform_shape(self.clone()) == *other)
+    }
+    /// Returns which pentomino this shape is an orientation of, if any
+    pub fn identify_pentomino(&self) -> Option<Pentomino> {
+        if self.size() != 5 {
+            return None;
+        }
+        let canonical = self.canonical();
+        PENTOMINOES
+            .iter()
+            .copied()
+            .find(|p| p.representative().canonical() == canonical)
+    }
+    /// Applies `n` quarter turns counter clockwise, negative `n` turning clockwise
+    pub fn rotate_90_times(&self, n: i32) -> CellShape {
+        // the shape is normalized afterwards, so the center doesn't matter
+        Transform::rotate_about(Coord::origin(), n).transform_shape(self.clone())
+    }
+    /// Whether `other`, moved by `offset`, shares an edge with this shape without overlapping it.
+    /// Shapes meeting only at a corner do not touch.
+    pub fn touches(&self, other: &CellShape, offset: Vec2D) -> bool {
+        let moved: Vec<Coord> = other.filled_tiles().map(|c| c + offset).collect();
+        if moved.iter().any(|&c| self[c].is_filled()) {
+            return false;
+        }
+        moved
+            .iter()
+            .flat_map(|c| c.neighbors4())
+            .any(|n| self[n].is_filled())
+    }
+    /// Whether every tile of this shape, moved by `offset`, is a filled tile of `board`
+    pub fn is_subset_of(&self, board: &CellShape, offset: Vec2D) -> bool {
+        self.filled_tiles().all(|c| board[c + offset].is_filled())
+    }
+    /// Adds the tiles of `other`, moved by `offset`, to this shape.
+    /// Fails with the first moved tile that is already filled, leaving the shape unchanged.
+    /// Like any shape, the result is normalized, so placing at negative coordinates shifts the existing tiles.
+    pub fn place_over(&mut self, other: &CellShape, offset: Vec2D) -> Result<(), Coord> {
+        let moved: Vec<Coord> = other.filled_tiles().map(|c| c + offset).collect();
+        if let Some(&collision) = moved.iter().find(|&&c| self[c].is_filled()) {
+            return Err(collision);
+        }
+        let mut tiles = self.tiles.clone();
+        tiles.extend(moved);
+        *self = CellShape::from_coordinate_list(tiles);
+        Ok(())
+    }
+    /// Like `place_over`, only reporting whether the placement fit
+    pub fn try_place_over(&mut self, other: &CellShape, offset: Vec2D) -> bool {
+        self.place_over(other, offset).is_ok()
+    }
+    /// The shape with every tile orthogonally next to it filled in as well
+    pub fn dilate(&self) -> CellShape {
+        let coords = self.filled_tiles()
+            .flat_map(|c| core::iter::once(c).chain(c.neighbors4()))
+            .collect();
+        CellShape::from_coordinate_list(coords)
+    }
+    /// The empty tiles orthogonally next to the shape, in sorted order.
+    /// These are the tiles `dilate` adds, but left in this shape's coordinates, so some are negative.
+    pub fn outer_frame(&self) -> Vec<Coord> {
+        let mut frame: Vec<Coord> = self.filled_tiles()
+            .flat_map(|c| c.neighbors4())
+            .filter(|&n| self[n].is_empty())
+            .collect();
+        frame.sort_by(coord_cmp);
+        frame.dedup();
+        frame
+    }
+    /// The shape without the filled tiles that are next to an empty tile
+    pub fn erode(&self) -> CellShape {
+        let coords = self.filled_tiles()
+            .filter(|c| c.neighbors4().iter().all(|&n| self[n].is_filled()))
+            .collect();
+        CellShape::from_coordinate_list(coords)
+    }
+    /// Like `try_place_over`, also rejecting placements that share an edge with the filled tiles
+    pub fn try_place_over_no_touch(&mut self, other: &CellShape, offset: Vec2D) -> bool {
+        !self.touches(other, offset) && self.try_place_over(other, offset)
+    }
+    /// The number of filled tiles whose four orthogonal neighbors are all filled
+    pub fn interior_cell_count(&self) -> usize {
+        self.filled_tiles()
+            .filter(|c| c.neighbors4().iter().all(|&n| self[n].is_filled()))
+            .count()
+    }
+    /// The number of filled tiles next to at least one empty tile
+    pub fn boundary_cell_count(&self) -> usize {
+        self.size() - self.interior_cell_count()
+    }
+    /// How many filled tiles are on each color of a checkerboard,
+    /// first those where `checker_parity` holds, then the rest
+    pub fn count_parity(&self) -> (usize, usize) {
+        let even = self.filled_tiles().filter(|&c| checker_parity(c)).count();
+        (even, self.size() - even)
+    }
+    /// The shape trimmed to its bounding box.
+    /// Shapes are always normalized, so this is the same shape.
+    pub fn crop(&self) -> CellShape {
+        self.clone()
+    }
+    /// The average x and y of the filled tiles, which is NaN for the empty shape
+    pub fn centroid(&self) -> (f64, f64) {
+        let n = self.size() as f64;
+        let sum_x: isize = self.filled_tiles().map(|c| c.x).sum();
+        let sum_y: isize = self.filled_tiles().map(|c| c.y).sum();
+        (sum_x as f64 / n, sum_y as f64 / n)
+    }
+    /// Blows up each tile into a `factor` by `factor` block
+    pub fn scale(&self, factor: usize) -> CellShape {
+        let factor = factor as isize;
+        let coords = self.filled_tiles()
+            .flat_map(|c| (0..factor * factor).map(move |i| Coord::new(
+                c.x * factor + i % factor,
+                c.y * factor + i / factor,
+            )))
+            .collect();
+        CellShape::from_coordinate_list(coords)
+    }
+    /// Fills `buf` with the tiles of the bounding box, row by row, reusing its allocations.
+    /// Rows are added or dropped until the buffer is exactly the size of the bounding box
+    pub fn render_into(&self, buf: &mut Vec<Vec<Tile>>) {
+        let (width, height) = self.bounding_box();
+        buf.resize_with(height as usize, Vec::new);
+        for row in buf.iter_mut() {
+            row.clear();
+            row.resize(width as usize, Tile::Empty);
+        }
+        for c in self.filled_tiles() {
+            buf[c.y as usize][c.x as usize] = Tile::Filled;
+        }
+    }
+    /// The shape blown up by each factor, as dense grids of rows covering the bounding box
+    pub fn to_grids_at_scales(&self, scales: &[usize]) -> Vec<Vec<Vec<Tile>>> {
+        scales
+            .iter()
+            .map(|&factor| {
+                let mut grid = Vec::new();
+                self.scale(factor).render_into(&mut grid);
+                grid
+            })
+            .collect()
+    }
+    /// Returns the width and height of the smallest rectangle containing the shape
+    pub fn bounding_box(&self) -> (isize, isize) {
+        if self.tiles.is_empty() {
+            return (0, 0);
+        }
+        let max = self.max();
+        (max.x + 1, max.y + 1)
+    }
+    /// The number of tiles in the bounding box
+    pub fn bounding_area(&self) -> usize {
+        let (width, height) = self.bounding_box();
+        (width * height) as usize
+    }
+    /// The fraction of the bounding box that is filled, which is NaN for the empty shape
+    pub fn density(&self) -> f64 {
+        self.size() as f64 / self.bounding_area() as f64
+    }
+    /// How compact the shape is, measured as its rectangularity: the filled fraction of the bounding box.
+    /// This is the same number as `density`, 1.0 only for rectangles, and NaN for the empty shape
+    pub fn convexity(&self) -> f64 {
+        self.density()
+    }
+    /// Draws the shape one row per line, using the given glyphs for filled and empty tiles
+    pub fn render(&self, filled: char, empty: char) -> String {
+        let max = self.max();
+        let mut out = String::new();
+        for c in coords_in_rect(Coord::origin(), max) {
+            let ch = match self[c] {
+                Tile::Filled => filled,
+                Tile::Empty => empty,
+            };
+            out.push(ch);
+            if c.x == max.x {
+                out.push('\n');
+            }
+        }
+        out
+    }
+    #[cfg(feature = "std")]
     pub fn print_out(&self) {
-        const FILLED: char = '█';
-        const EMPTY: char = ' ';
+        for line in self.render(FILLED, EMPTY).lines() {
+            println!("  {}", line);
+        }
+    }
+    /// Renders the shape with row and column indices, marking the origin with `+`.
+    /// The x axis points right and the y axis points down, matching the transforms.
+    pub fn render_with_axes(&self) -> String {
+        const EMPTY: char = '.';
 
         let max = self.max();
+        let label_width = max.y.to_string().len();
+
+        let mut out = String::new();
+        out.push_str(&" ".repeat(label_width));
+        out.push('+');
+        for x in 0..=max.x {
+            // only the last digit fits above each column
+            out.push_str(&(x % 10).to_string());
+        }
+        out.push_str(" x\n");
+
         for y in 0..=max.y {
-            print!("  ");
+            out.push_str(&format!("{:>label_width$}|", y));
             for x in 0..=max.x {
-
                 let ch = match self[Coord::new(x,y)] {
                     Tile::Filled => FILLED,
                     Tile::Empty => EMPTY,
                 };
-                print!("{}", ch);
+                out.push(ch);
+            }
+            out.push('\n');
+        }
+        out.push_str(&format!("{:>label_width$}\n", "y"));
+        out
+    }
+    #[cfg(feature = "std")]
+    pub fn print_with_axes(&self) {
+        print!("{}", self.render_with_axes());
+    }
+}
 
+/// The color of the tile on a checkerboard, true when x + y is even
+pub fn checker_parity(coord: Coord) -> bool {
+    (coord.x + coord.y).rem_euclid(2) == 0
+}
+
+/// Groups the tiles of an ascii grid by their character, such as `#` for fillable tiles and `.` for forbidden ones.
+/// Spaces are skipped. Each region is normalized like any shape, so their positions relative to each other are lost.
+#[cfg(feature = "std")]
+pub fn regions_from_ascii(s: &str) -> HashMap<char, CellShape> {
+    let mut regions: HashMap<char, Vec<Coord>> = HashMap::new();
+    for (y, line) in s.lines().enumerate() {
+        for (x, ch) in line.chars().enumerate() {
+            if ch != ' ' {
+                regions.entry(ch).or_default().push(Coord::new(x as isize, y as isize));
             }
-            println!();
         }
     }
+    regions
+        .into_iter()
+        .map(|(ch, coords)| (ch, CellShape::from_coordinate_list(coords)))
+        .collect()
 }
 
-impl std::ops::Index<Coord> for CellShape {
+/// Trims the empty rows and columns from the borders of a dense grid.
+/// Ragged rows are padded with empty tiles, and an empty grid comes back when nothing is filled.
+pub fn crop_grid(grid: &[Vec<Tile>]) -> Vec<Vec<Tile>> {
+    let filled: Vec<Coord> = grid
+        .iter()
+        .enumerate()
+        .flat_map(|(y, row)| row
+            .iter()
+            .enumerate()
+            .filter(|(_, tile)| tile.is_filled())
+            .map(move |(x, _)| Coord::new(x as isize, y as isize)))
+        .collect();
+    let (Some(min), Some(max)) = (
+        filled.iter().copied().reduce(Coord::component_min),
+        filled.iter().copied().reduce(Coord::component_max),
+    ) else {
+        return Vec::new();
+    };
+    (min.y..=max.y)
+        .map(|y| (min.x..=max.x)
+            .map(|x| grid[y as usize].get(x as usize).copied().unwrap_or(Tile::Empty))
+            .collect())
+        .collect()
+}
+
+/// Draws the shapes side by side, top aligned, with `gap` columns of space between them
+pub fn render_row(shapes: &[CellShape], gap: usize) -> String {
+    let height = shapes.iter().map(|s| s.bounding_box().1).max().unwrap_or(0);
+    let mut out = String::new();
+    for y in 0..height {
+        for (i, shape) in shapes.iter().enumerate() {
+            if i > 0 {
+                out.push_str(&" ".repeat(gap));
+            }
+            let (width, _) = shape.bounding_box();
+            for x in 0..width {
+                let ch = match shape[Coord::new(x, y)] {
+                    Tile::Filled => FILLED,
+                    Tile::Empty => EMPTY,
+                };
+                out.push(ch);
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+impl core::fmt::Display for CellShape {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.render(FILLED, EMPTY))
+    }
+}
+
+impl Ord for CellShape {
+    /// Compares the sorted tile lists lexicographically
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.tiles
+            .iter()
+            .zip(other.tiles.iter())
+            .map(|(lhs, rhs)| coord_cmp(lhs, rhs))
+            .find(|ord| ord.is_ne())
+            .unwrap_or_else(|| self.tiles.len().cmp(&other.tiles.len()))
+    }
+}
+
+impl PartialOrd for CellShape {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl core::ops::Index<Coord> for CellShape {
     type Output = Tile;
 
     fn index(&self, coord: Coord) -> &Self::Output {
@@ -109,6 +613,24 @@ impl std::ops::Index<Coord> for CellShape {
 mod tests {
     use super::*;
 
+    #[test]
+    fn tile_is_filled() {
+        assert!(Tile::Filled.is_filled());
+        assert!(!Tile::Empty.is_filled());
+    }
+
+    #[test]
+    fn tile_is_empty() {
+        assert!(Tile::Empty.is_empty());
+        assert!(!Tile::Filled.is_empty());
+    }
+
+    #[test]
+    fn tile_toggle() {
+        assert_eq!(Tile::Filled.toggle(), Tile::Empty);
+        assert_eq!(Tile::Empty.toggle(), Tile::Filled);
+        assert_eq!(Tile::Filled.toggle().toggle(), Tile::Filled);
+    }
 
     #[test]
     fn it_works() {
@@ -209,6 +731,648 @@ mod tests {
 
     }
 
+    #[test]
+    fn translate_to() {
+        use Tile::*;
+
+        // the L pentomino
+        let tg = CellShape::from_2darray([
+            [Filled, Empty],
+            [Filled, Empty],
+            [Filled, Empty],
+            [Filled, Filled],
+        ]);
+
+        let coords = tg.translate_to(Coord { x: 3, y: 4 });
+
+        assert_eq!(coords.len(), 5);
+        assert!(coords.contains(&Coord { x: 3, y: 4 }));
+        assert!(coords.contains(&Coord { x: 4, y: 7 }));
+        assert!(!coords.contains(&Coord { x: 4, y: 4 }));
+    }
+
+    #[test]
+    fn render_with_axes() {
+        use Tile::*;
+
+        let tg = CellShape::from_2darray([
+            [Filled, Empty,  Empty],
+            [Filled, Filled, Filled],
+        ]);
+
+        let expected = concat!(
+            " +012 x\n",
+            "0|█..\n",
+            "1|███\n",
+            "y\n",
+        );
+
+        assert_eq!(tg.render_with_axes(), expected);
+    }
+
+    #[test]
+    fn u64_round_trip() {
+        for p in crate::pentomino::PENTOMINOES {
+            for shape in p.shapes() {
+                let (width, _) = shape.bounding_box();
+                let bits = shape.to_u64().unwrap();
+                assert_eq!(bits.count_ones(), 5);
+                assert_eq!(CellShape::from_u64(bits, width as u8), shape);
+            }
+        }
+    }
+
+    #[test]
+    fn u64_too_large() {
+        let tg = CellShape::from_coordinate_list(vec![
+            Coord { x: 0, y: 0 },
+            Coord { x: 8, y: 8 },
+        ]);
+        assert_eq!(tg.to_u64(), None);
+    }
+
+    #[test]
+    fn scale() {
+        use Tile::*;
+
+        let tg = CellShape::from_2darray([
+            [Filled, Empty],
+            [Filled, Filled],
+        ]);
+
+        let expected = CellShape::from_2darray([
+            [Filled, Filled, Empty,  Empty],
+            [Filled, Filled, Empty,  Empty],
+            [Filled, Filled, Filled, Filled],
+            [Filled, Filled, Filled, Filled],
+        ]);
+
+        assert_eq!(tg.scale(2), expected);
+        assert_eq!(tg.scale(1), tg);
+    }
+
+    #[test]
+    fn extent() {
+        use Tile::*;
+
+        // the Z pentomino
+        let tg = CellShape::from_2darray([
+            [Filled, Filled, Empty],
+            [Empty,  Filled, Empty],
+            [Empty,  Filled, Filled],
+        ]);
+
+        assert_eq!(tg.extent(), (Coord { x: 0, y: 0 }, Coord { x: 2, y: 2 }));
+    }
+
+    #[test]
+    fn flip_lr() {
+        use Tile::*;
+
+        let l = CellShape::from_2darray([
+            [Filled, Empty],
+            [Filled, Empty],
+            [Filled, Empty],
+            [Filled, Filled],
+        ]);
+
+        let j = CellShape::from_2darray([
+            [Empty,  Filled],
+            [Empty,  Filled],
+            [Empty,  Filled],
+            [Filled, Filled],
+        ]);
+
+        assert_eq!(l.flip_lr(), j);
+        assert_eq!(l.flip_lr().bounding_box(), l.bounding_box());
+        assert_eq!(l.flip_lr().flip_lr(), l);
+    }
+
+    #[test]
+    fn flip_ud() {
+        use Tile::*;
+
+        let l = CellShape::from_2darray([
+            [Filled, Empty],
+            [Filled, Empty],
+            [Filled, Empty],
+            [Filled, Filled],
+        ]);
+
+        let expected = CellShape::from_2darray([
+            [Filled, Filled],
+            [Filled, Empty],
+            [Filled, Empty],
+            [Filled, Empty],
+        ]);
+
+        assert_eq!(l.flip_ud(), expected);
+        assert_eq!(l.flip_ud().flip_ud(), l);
+    }
+
+    #[test]
+    fn ordering() {
+        use std::collections::BTreeSet;
+
+        let mut forward: Vec<CellShape> = crate::pentomino::PENTOMINOES
+            .iter()
+            .flat_map(|p| p.shapes())
+            .collect();
+        let mut backward = forward.clone();
+        backward.reverse();
+
+        forward.sort();
+        backward.sort();
+        assert_eq!(forward, backward);
+        assert!(forward.windows(2).all(|w| w[0] < w[1]));
+
+        let set: BTreeSet<CellShape> = forward.iter().cloned().collect();
+        assert!(set.into_iter().eq(forward));
+    }
+
+    #[test]
+    fn ordering_prefix() {
+        let shorter = CellShape::from_coordinate_list(vec![Coord::new(0, 0)]);
+        let longer = CellShape::from_coordinate_list(vec![Coord::new(0, 0), Coord::new(0, 1)]);
+        assert!(shorter < longer);
+        assert!(CellShape::empty() < shorter);
+    }
+
+    #[test]
+    fn identify_pentomino() {
+        for p in PENTOMINOES {
+            for shape in p.shapes() {
+                assert_eq!(shape.identify_pentomino(), Some(p));
+            }
+        }
+    }
+
+    #[test]
+    fn identify_pentomino_rejects_tetromino() {
+        use Tile::*;
+
+        let t_tetromino = CellShape::from_2darray([
+            [Filled, Filled, Filled],
+            [Empty,  Filled, Empty],
+        ]);
+        assert_eq!(t_tetromino.identify_pentomino(), None);
+
+        // five tiles, but not connected
+        let scattered = CellShape::from_2darray([
+            [Filled, Empty,  Filled],
+            [Empty,  Filled, Empty],
+            [Filled, Empty,  Filled],
+        ]);
+        assert_eq!(scattered.identify_pentomino(), None);
+    }
+
+    #[test]
+    fn render_custom_glyphs() {
+        use Tile::*;
+
+        // the X pentomino
+        let tg = CellShape::from_2darray([
+            [Empty,  Filled, Empty],
+            [Filled, Filled, Filled],
+            [Empty,  Filled, Empty],
+        ]);
+
+        assert_eq!(tg.render('1', '0'), "010\n111\n010\n");
+        assert_eq!(tg.to_string(), " █ \n███\n █ \n");
+    }
+
+    #[test]
+    fn render_row_side_by_side() {
+        use Tile::*;
+
+        let i = CellShape::from_2darray([
+            [Filled],
+            [Filled],
+            [Filled],
+            [Filled],
+            [Filled],
+        ]);
+        let x = CellShape::from_2darray([
+            [Empty,  Filled, Empty],
+            [Filled, Filled, Filled],
+            [Empty,  Filled, Empty],
+        ]);
+
+        let out = render_row(&[i, x], 2);
+        let lines: Vec<&str> = out.lines().collect();
+
+        assert_eq!(lines.len(), 5);
+        assert!(lines.iter().all(|l| l.chars().count() == 1 + 2 + 3));
+        assert_eq!(lines[1], "█  ███");
+        assert_eq!(lines[4], "█     ");
+    }
+
+    #[test]
+    fn rotate_90_times() {
+        use Tile::*;
+
+        let tg = CellShape::from_2darray([
+            [Filled, Empty],
+            [Filled, Empty],
+            [Filled, Empty],
+            [Filled, Filled],
+        ]);
+
+        let rotate90 = Transform::rotate90().transform_shape(tg.clone());
+        let rotate270 = Transform::rotate270().transform_shape(tg.clone());
+
+        assert_eq!(tg.rotate_90_times(0), tg);
+        assert_eq!(tg.rotate_90_times(1), rotate90);
+        assert_eq!(tg.rotate_90_times(5), rotate90);
+        assert_eq!(tg.rotate_90_times(-1), rotate270);
+        assert_eq!(tg.rotate_90_times(-4), tg);
+    }
+
+    #[test]
+    fn touches() {
+        use Tile::*;
+
+        let domino = CellShape::from_2darray([
+            [Filled, Filled],
+        ]);
+
+        // sharing an edge
+        assert!(domino.touches(&domino, Vec2D::new(2, 0)));
+        assert!(domino.touches(&domino, Vec2D::new(1, 1)));
+        // only a corner
+        assert!(!domino.touches(&domino, Vec2D::new(2, 1)));
+        // far apart
+        assert!(!domino.touches(&domino, Vec2D::new(5, 5)));
+        // overlapping
+        assert!(!domino.touches(&domino, Vec2D::new(1, 0)));
+    }
+
+    #[test]
+    fn interior_and_boundary_i_pentomino() {
+        let i = crate::pentomino::Pentomino::I.representative();
+        assert_eq!(i.interior_cell_count(), 0);
+        assert_eq!(i.boundary_cell_count(), 5);
+    }
+
+    #[test]
+    fn interior_and_boundary_square() {
+        let square = CellShape::rectangle(3, 3);
+        assert_eq!(square.interior_cell_count(), 1);
+        assert_eq!(square.boundary_cell_count(), 8);
+    }
+
+    #[test]
+    fn cell_index_round_trip() {
+        let tg = crate::pentomino::Pentomino::F.representative();
+
+        let index = tg.cell_index();
+        let cells = tg.index_to_cell();
+
+        let mut indices: Vec<usize> = index.values().copied().collect();
+        indices.sort();
+        assert_eq!(indices, (0..5).collect::<Vec<_>>());
+        for (i, c) in cells.iter().enumerate() {
+            assert_eq!(index[c], i);
+        }
+    }
+
+    #[test]
+    fn crop_padded_grid() {
+        use Tile::*;
+
+        let grid = vec![
+            vec![Empty, Empty,  Empty,  Empty],
+            vec![Empty, Filled, Empty,  Empty],
+            vec![Empty, Filled, Filled],
+            vec![Empty, Empty,  Empty,  Empty],
+        ];
+
+        let expected = vec![
+            vec![Filled, Empty],
+            vec![Filled, Filled],
+        ];
+
+        assert_eq!(crop_grid(&grid), expected);
+        assert_eq!(crop_grid(&[vec![Empty, Empty]]), Vec::<Vec<Tile>>::new());
+    }
+
+    #[test]
+    fn crop_shape() {
+        let tg = crate::pentomino::Pentomino::W.representative();
+        assert_eq!(tg.crop(), tg);
+    }
+
+    #[test]
+    fn centroid() {
+        use crate::pentomino::Pentomino;
+
+        assert_eq!(Pentomino::X.representative().centroid(), (1.0, 1.0));
+        assert_eq!(Pentomino::I.representative().centroid(), (0.0, 2.0));
+        assert_eq!(Pentomino::L.representative().centroid(), (0.2, 1.8));
+    }
+
+    #[test]
+    fn cells_u_pentomino() {
+        use Tile::*;
+
+        let u = crate::pentomino::Pentomino::U.representative();
+        let cells: Vec<(Coord, Tile)> = u.cells().collect();
+
+        assert_eq!(cells, vec![
+            (Coord { x: 0, y: 0 }, Filled),
+            (Coord { x: 1, y: 0 }, Empty),
+            (Coord { x: 2, y: 0 }, Filled),
+            (Coord { x: 0, y: 1 }, Filled),
+            (Coord { x: 1, y: 1 }, Filled),
+            (Coord { x: 2, y: 1 }, Filled),
+        ]);
+        assert_eq!(CellShape::empty().cells().count(), 0);
+    }
+
+    #[test]
+    fn from_grid() {
+        use Tile::*;
+
+        // a staircase, built at runtime
+        let grid: Vec<Vec<Tile>> = (0..3)
+            .map(|y| (0..=y).map(|x| if x == y { Filled } else { Empty }).collect())
+            .collect();
+
+        let expected = CellShape::from_2darray([
+            [Filled, Empty,  Empty],
+            [Empty,  Filled, Empty],
+            [Empty,  Empty,  Filled],
+        ]);
+
+        assert_eq!(CellShape::from_grid(&grid), expected);
+        assert_eq!(CellShape::from_grid(&[]), CellShape::empty());
+    }
+
+    #[test]
+    fn shift_into_positive() {
+        let mut coords = vec![Coord::new(-2, 3), Coord::new(0, -1), Coord::new(1, 1)];
+        let shift = CellShape::shift_into_positive(&mut coords);
+        assert_eq!(shift, Vec2D::new(2, 1));
+        assert_eq!(coords, vec![Coord::new(0, 4), Coord::new(2, 0), Coord::new(3, 2)]);
+
+        let mut empty = Vec::new();
+        assert_eq!(CellShape::shift_into_positive(&mut empty), Vec2D::zero());
+    }
+
+    #[test]
+    fn count_parity() {
+        assert!(checker_parity(Coord::origin()));
+        assert!(!checker_parity(Coord::new(-1, 0)));
+        assert_eq!(CellShape::rectangle(6, 10).count_parity(), (30, 30));
+        assert_eq!(Pentomino::X.representative().count_parity(), (1, 4));
+    }
+
+    #[test]
+    fn place_over() {
+        let mut board = CellShape::rectangle(2, 1);
+        let i = Pentomino::I.representative();
+        assert!(board.try_place_over(&i, Vec2D::new(2, 0)));
+        assert_eq!(board.size(), 7);
+        assert_eq!(board[Coord::new(2, 4)], Tile::Filled);
+
+        assert_eq!(board.place_over(&i, Vec2D::new(1, 0)), Err(Coord::new(1, 0)));
+        assert_eq!(board.place_over(&i, Vec2D::new(2, 3)), Err(Coord::new(2, 3)));
+        assert!(!board.try_place_over(&i, Vec2D::new(0, -4)));
+        assert_eq!(board.size(), 7);
+    }
+
+    #[test]
+    fn bounding_area_and_density() {
+        let i = Pentomino::I.representative();
+        assert_eq!(i.bounding_area(), 5);
+        assert_eq!(i.density(), 1.0);
+
+        let x = Pentomino::X.representative();
+        assert_eq!(x.bounding_area(), 9);
+        assert_eq!(x.density(), 5.0 / 9.0);
+    }
+
+    #[test]
+    fn flip_diagonals() {
+        use Tile::*;
+        let l = Pentomino::L.representative();
+        assert_eq!(l.flip_main_diagonal(), CellShape::from_2darray([
+            [Filled, Filled, Filled, Filled],
+            [Empty, Empty, Empty, Filled],
+        ]));
+        assert_eq!(l.flip_anti_diagonal(), CellShape::from_2darray([
+            [Filled, Empty, Empty, Empty],
+            [Filled, Filled, Filled, Filled],
+        ]));
+        assert_eq!(l.flip_main_diagonal().flip_main_diagonal(), l);
+        assert_eq!(l.flip_anti_diagonal().flip_anti_diagonal(), l);
+    }
+
+    #[test]
+    fn regions_from_ascii() {
+        let regions = super::regions_from_ascii("##.\n#..\nxx \n");
+        assert_eq!(regions.len(), 3);
+        assert_eq!(regions[&'#'], CellShape::from_coordinate_list(vec![
+            Coord::new(0, 0), Coord::new(1, 0), Coord::new(0, 1),
+        ]));
+        assert_eq!(regions[&'.'], CellShape::from_coordinate_list(vec![
+            Coord::new(2, 0), Coord::new(1, 1), Coord::new(2, 1),
+        ]));
+        assert_eq!(regions[&'x'], CellShape::rectangle(2, 1));
+    }
+
+    #[test]
+    fn dilate_and_erode() {
+        let dot = CellShape::rectangle(1, 1);
+        let plus = dot.dilate();
+        assert_eq!(plus, Pentomino::X.representative());
+        assert_eq!(plus.erode().size(), 1);
+        assert_eq!(dot.erode(), CellShape::empty());
+        assert_eq!(CellShape::rectangle(3, 3).erode(), dot);
+        assert_eq!(CellShape::empty().dilate(), CellShape::empty());
+    }
+
+    #[test]
+    fn from_coordinate_list_canonical() {
+        let coords = Pentomino::F.representative().translate_to(Coord::new(-3, 7));
+        let turned = Transform::rotate90().transform_shape(Pentomino::F.representative()).translate_to(Coord::new(2, 2));
+        let lhs = CellShape::from_coordinate_list_canonical(coords);
+        assert_eq!(lhs, CellShape::from_coordinate_list_canonical(turned));
+        assert_eq!(lhs, Pentomino::F.representative().canonical());
+    }
+
+    #[test]
+    fn outer_frame() {
+        assert_eq!(CellShape::rectangle(1, 1).outer_frame(), vec![
+            Coord::new(-1, 0),
+            Coord::new(0, -1),
+            Coord::new(0, 1),
+            Coord::new(1, 0),
+        ]);
+        let l = Pentomino::L.representative();
+        assert_eq!(l.outer_frame().len(), l.dilate().size() - l.size());
+    }
+
+    #[test]
+    fn place_over_no_touch() {
+        let mut board = CellShape::rectangle(2, 1);
+        let i = Pentomino::I.representative();
+        assert!(!board.try_place_over_no_touch(&i, Vec2D::new(2, 0)));
+        // touching only at a corner is allowed
+        assert!(board.try_place_over_no_touch(&i, Vec2D::new(2, 1)));
+        assert!(board.try_place_over_no_touch(&i, Vec2D::new(4, 0)));
+        assert!(!board.try_place_over_no_touch(&i, Vec2D::new(4, 0)));
+        assert_eq!(board.size(), 12);
+    }
+
+    #[test]
+    fn map_coords_shear() {
+        // shearing each row one tile further right than the row above
+        let sheared = CellShape::rectangle(2, 3).map_coords(|c| Coord::new(c.x + c.y, c.y));
+        let expected: Vec<Coord> = vec![
+            Coord::new(0, 0), Coord::new(1, 0),
+            Coord::new(1, 1), Coord::new(2, 1),
+            Coord::new(2, 2), Coord::new(3, 2),
+        ];
+        assert_eq!(sheared.filled_tiles().collect::<Vec<_>>(), expected);
+        // negative coordinates are shifted back into place
+        assert_eq!(CellShape::rectangle(2, 3).map_coords(|c| Coord::new(-c.x, c.y)), CellShape::rectangle(2, 3));
+    }
+
+    #[test]
+    fn grids_at_scales() {
+        use Tile::*;
+
+        let domino = CellShape::from_2darray([
+            [Filled, Filled],
+        ]);
+        let grids = domino.to_grids_at_scales(&[1, 2]);
+        assert_eq!(grids.len(), 2);
+        assert_eq!(grids[0], vec![vec![Filled, Filled]]);
+        assert_eq!(grids[1], vec![vec![Filled; 4]; 2]);
+        assert_eq!(CellShape::from_grid(&grids[1]), domino.scale(2));
+    }
+
+    #[test]
+    fn is_subset_of() {
+        let board = CellShape::rectangle(6, 10);
+        let l = Pentomino::L.representative();
+        let (width, height) = l.bounding_box();
+        assert!(l.is_subset_of(&board, Vec2D::zero()));
+        assert!(l.is_subset_of(&board, Vec2D::new(6 - width, 10 - height)));
+        // hanging off the right edge or the top
+        assert!(!l.is_subset_of(&board, Vec2D::new(7 - width, 0)));
+        assert!(!l.is_subset_of(&board, Vec2D::new(0, -1)));
+        assert!(CellShape::empty().is_subset_of(&board, Vec2D::new(-5, -5)));
+    }
+
+    #[test]
+    fn assemble_clean() {
+        let board = CellShape::assemble(&[
+            (CellShape::rectangle(3, 2), Vec2D::zero()),
+            (CellShape::rectangle(3, 2), Vec2D::new(0, 2)),
+            (CellShape::rectangle(1, 1), Vec2D::new(-1, 0)),
+        ]);
+        assert_eq!(board, Ok(CellShape::from_coordinate_list(
+            coords_in_rect(Coord::new(1, 0), Coord::new(3, 3)).chain([Coord::origin()]).collect()
+        )));
+        assert_eq!(CellShape::assemble(&[]), Ok(CellShape::empty()));
+    }
+
+    #[test]
+    fn assemble_overlapping() {
+        let board = CellShape::assemble(&[
+            (CellShape::rectangle(3, 2), Vec2D::zero()),
+            (CellShape::rectangle(2, 2), Vec2D::new(-1, 1)),
+        ]);
+        assert_eq!(board, Err(Coord::new(0, 1)));
+    }
+
+    #[test]
+    fn transform_to() {
+        let l = Pentomino::L.representative();
+        let turned = Transform::rotate90().transform_shape(l.clone());
+        assert_eq!(l.transform_to(&turned), Some(Transform::rotate90()));
+        assert_eq!(turned.transform_to(&l), Some(Transform::rotate270()));
+        assert_eq!(l.transform_to(&l), Some(Transform::identity()));
+        assert_eq!(l.transform_to(&Pentomino::N.representative()), None);
+    }
+
+    #[test]
+    fn render_into_reuses_buffer() {
+        use Tile::*;
+
+        let l = Pentomino::L.representative();
+        let mut buf = vec![vec![Filled; 2]; 4];
+        let (rows, row) = (buf.as_ptr(), buf[0].as_ptr());
+        l.render_into(&mut buf);
+        l.render_into(&mut buf);
+        assert_eq!((buf.as_ptr(), buf[0].as_ptr()), (rows, row));
+        assert_eq!(buf, vec![
+            vec![Filled, Empty],
+            vec![Filled, Empty],
+            vec![Filled, Empty],
+            vec![Filled, Filled],
+        ]);
+
+        // a smaller shape shrinks the buffer to fit
+        CellShape::rectangle(3, 1).render_into(&mut buf);
+        assert_eq!(buf, vec![vec![Filled; 3]]);
+    }
+
+    #[test]
+    fn row_and_col_masks() {
+        let i = Pentomino::I.representative();
+        assert_eq!(i.col_mask(0), 0b11111);
+        assert!((0..5).all(|y| i.row_mask(y) == 0b1));
+        assert_eq!(i.col_mask(1), 0);
+
+        let x = Pentomino::X.representative();
+        assert_eq!(x.row_mask(0), 0b010);
+        assert_eq!(x.row_mask(1), 0b111);
+        assert_eq!(x.col_mask(2), 0b010);
+        assert_eq!(x.row_mask(3), 0);
+        assert_eq!(x.row_mask(-1), 0);
+    }
+
+    #[test]
+    fn orientations_borrowed() {
+        for p in PENTOMINOES {
+            let borrowed: Vec<CellShape> = p.representative().orientations_borrowed().collect();
+            assert_eq!(borrowed.len(), p.shapes().len());
+            assert_eq!(borrowed.into_iter().collect::<std::collections::HashSet<_>>(), p.shapes());
+        }
+        let first = Pentomino::L.representative().orientations_borrowed().next();
+        assert_eq!(first, Some(Pentomino::L.representative()));
+    }
+
+    #[test]
+    fn orientations_modulo() {
+        let rectangle = [
+            Transform::identity(),
+            Transform::mirror_horizontal(),
+            Transform::mirror_vertical(),
+            Transform::rotate180(),
+        ];
+        for p in PENTOMINOES {
+            // a square board turns any orientation into any other
+            assert_eq!(p.representative().orientations_modulo(&RIGID_SYMMETRIES).len(), 1, "{:?}", p);
+            assert_eq!(p.representative().orientations_modulo(&[]).len(), p.shapes().len());
+        }
+        // lying down and standing up are different on a rectangle
+        assert_eq!(Pentomino::F.representative().orientations_modulo(&rectangle).len(), 2);
+        assert_eq!(Pentomino::I.representative().orientations_modulo(&rectangle).len(), 2);
+        assert_eq!(Pentomino::X.representative().orientations_modulo(&rectangle).len(), 1);
+    }
+
+    #[test]
+    fn convexity() {
+        assert_eq!(Pentomino::I.representative().convexity(), 1.0);
+        assert_eq!(Pentomino::X.representative().convexity(), 5.0 / 9.0);
+        assert_eq!(Pentomino::P.representative().convexity(), 5.0 / 6.0);
+        assert!(CellShape::empty().convexity().is_nan());
+    }
+
     /*
 
     #[test]
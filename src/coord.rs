@@ -1,3 +1,5 @@
+use alloc::vec::Vec;
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct Coord {
     pub x: isize,
@@ -10,20 +12,68 @@ impl Coord {
     pub const fn origin() -> Self {
         Self { x: 0, y: 0 }
     }
+    /// The four orthogonally adjacent coordinates
+    pub fn neighbors4(self) -> [Coord; 4] {
+        CARDINALS.map(|v| self + v)
+    }
+    /// The orthogonally adjacent coordinates inside the inclusive rectangle between `min` and `max`
+    pub fn neighbors4_in(self, min: Coord, max: Coord) -> Vec<Coord> {
+        self.neighbors4()
+            .into_iter()
+            .filter(|n| (min.x..=max.x).contains(&n.x) && (min.y..=max.y).contains(&n.y))
+            .collect()
+    }
+    /// The smallest x and the smallest y of the two coordinates
+    pub fn component_min(self, other: Coord) -> Coord {
+        Coord {
+            x: self.x.min(other.x),
+            y: self.y.min(other.y),
+        }
+    }
+    /// The largest x and the largest y of the two coordinates
+    pub fn component_max(self, other: Coord) -> Coord {
+        Coord {
+            x: self.x.max(other.x),
+            y: self.y.max(other.y),
+        }
+    }
+    /// The coordinate moved by `v`, like `+` but usable in const contexts
+    pub const fn add_vec(self, v: Vec2D) -> Coord {
+        Coord {
+            x: self.x + v.x,
+            y: self.y + v.y,
+        }
+    }
+    /// Clamps each component into the inclusive range between `min` and `max`.
+    /// Panics when `min` is greater than `max` in either component, as for an empty rectangle
+    pub fn clamp(self, min: Coord, max: Coord) -> Coord {
+        Coord {
+            x: self.x.clamp(min.x, max.x),
+            y: self.y.clamp(min.y, max.y),
+        }
+    }
+}
+
+/// Every coordinate in the inclusive rectangle between `min` and `max`, row by row
+pub fn coords_in_rect(min: Coord, max: Coord) -> impl Iterator<Item = Coord> {
+    (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Coord::new(x, y)))
 }
 
-impl std::ops::Add<Vec2D> for Coord {
+impl core::fmt::Display for Coord {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl core::ops::Add<Vec2D> for Coord {
     type Output = Coord;
 
     fn add(self, rhs: Vec2D) -> Self::Output {
-        Coord {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y
-        }
+        self.add_vec(rhs)
     }
 }
 
-impl std::ops::Sub for Coord {
+impl core::ops::Sub for Coord {
     type Output = Vec2D;
 
     fn sub(self, rhs: Self) -> Self::Output {
@@ -34,32 +84,74 @@ impl std::ops::Sub for Coord {
     }
 }
 
+/// The steps to the four orthogonally adjacent tiles
+pub const CARDINALS: [Vec2D; 4] = [Vec2D::RIGHT, Vec2D::LEFT, Vec2D::DOWN, Vec2D::UP];
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct Vec2D {
     pub x: isize,
     pub y: isize,
 }
 impl Vec2D {
+    /// One tile up. The y axis points downwards, like the screen
+    pub const UP: Vec2D = Vec2D::new(0, -1);
+    /// One tile down
+    pub const DOWN: Vec2D = Vec2D::new(0, 1);
+    /// One tile left
+    pub const LEFT: Vec2D = Vec2D::new(-1, 0);
+    /// One tile right
+    pub const RIGHT: Vec2D = Vec2D::new(1, 0);
+
     pub const fn new(x: isize, y: isize) -> Self {
         Self { x, y }
     }
     pub const fn zero() -> Self {
         Self { x: 0, y: 0 }
     }
+    /// The sum of the vectors, like `+` but usable in const contexts
+    pub const fn add(self, o: Vec2D) -> Vec2D {
+        Vec2D {
+            x: self.x + o.x,
+            y: self.y + o.y,
+        }
+    }
+    /// Component-wise absolute value
+    pub const fn abs(self) -> Vec2D {
+        Vec2D {
+            x: self.x.abs(),
+            y: self.y.abs(),
+        }
+    }
+    /// The dot product, zero when the vectors are perpendicular
+    pub const fn dot(self, other: Vec2D) -> isize {
+        self.x * other.x + self.y * other.y
+    }
+    /// The 2D cross product (perp-dot), zero when the vectors are parallel.
+    /// Keep in mind the y axis points downwards, so it is positive for a clockwise turn from `self` to `other`
+    pub const fn cross(self, other: Vec2D) -> isize {
+        self.x * other.y - self.y * other.x
+    }
+    /// The larger of the two components
+    pub fn max_component(self) -> isize {
+        self.x.max(self.y)
+    }
+}
+
+impl core::fmt::Display for Vec2D {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "<{}, {}>", self.x, self.y)
+    }
 }
 
-impl std::ops::Add for Vec2D {
+impl core::ops::Add for Vec2D {
     type Output = Vec2D;
 
     fn add(self, rhs: Self) -> Self::Output {
-        Self {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y
-        }
+        Vec2D::add(self, rhs)
     }
 }
 
-impl std::ops::Sub for Vec2D {
+impl core::ops::Sub for Vec2D {
     type Output = Vec2D;
 
     fn sub(self, rhs: Self) -> Self::Output {
@@ -88,4 +180,118 @@ mod tests {
         let rhs = Coord { x: -1, y: 7 };
         assert_eq!(lhs - rhs, Vec2D { x: 4, y: -2 });
     }
+
+    #[test]
+    fn clamp_outside() {
+        let min = Coord { x: 0, y: 0 };
+        let max = Coord { x: 9, y: 5 };
+        assert_eq!(Coord { x: 12, y: -3 }.clamp(min, max), Coord { x: 9, y: 0 });
+        assert_eq!(Coord { x: -1, y: 8 }.clamp(min, max), Coord { x: 0, y: 5 });
+    }
+
+    #[test]
+    fn clamp_inside() {
+        let min = Coord { x: 0, y: 0 };
+        let max = Coord { x: 9, y: 5 };
+        assert_eq!(Coord { x: 4, y: 2 }.clamp(min, max), Coord { x: 4, y: 2 });
+    }
+
+    #[test]
+    fn component_min_max() {
+        let lhs = Coord { x: 3, y: -5 };
+        let rhs = Coord { x: -1, y: 7 };
+        assert_eq!(lhs.component_min(rhs), Coord { x: -1, y: -5 });
+        assert_eq!(lhs.component_max(rhs), Coord { x: 3, y: 7 });
+    }
+
+    #[test]
+    fn abs() {
+        assert_eq!(Vec2D { x: -3, y: 4 }.abs(), Vec2D { x: 3, y: 4 });
+        assert_eq!(Vec2D { x: 2, y: -7 }.abs(), Vec2D { x: 2, y: 7 });
+    }
+
+    #[test]
+    fn max_component() {
+        assert_eq!(Vec2D { x: -3, y: 4 }.max_component(), 4);
+        assert_eq!(Vec2D { x: -3, y: -8 }.max_component(), -3);
+        assert_eq!((Coord { x: 1, y: 2 } - Coord { x: 4, y: 0 }).abs().max_component(), 3);
+    }
+
+    #[test]
+    fn dot() {
+        assert_eq!(Vec2D { x: 1, y: 0 }.dot(Vec2D { x: 0, y: 1 }), 0);
+        assert_eq!(Vec2D { x: 2, y: 3 }.dot(Vec2D { x: -3, y: 2 }), 0);
+        assert_eq!(Vec2D { x: 2, y: 3 }.dot(Vec2D { x: 4, y: 5 }), 23);
+    }
+
+    #[test]
+    fn cross() {
+        let right = Vec2D { x: 1, y: 0 };
+        let down = Vec2D { x: 0, y: 1 };
+        assert_eq!(right.cross(down), 1);
+        assert_eq!(down.cross(right), -1);
+        assert_eq!(Vec2D { x: 2, y: 4 }.cross(Vec2D { x: 1, y: 2 }), 0);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Coord { x: 3, y: 5 }.to_string(), "(3, 5)");
+        assert_eq!(Vec2D { x: -3, y: 5 }.to_string(), "<-3, 5>");
+    }
+
+    #[test]
+    fn neighbors4() {
+        let neighbors = Coord { x: 3, y: 5 }.neighbors4();
+        assert!(neighbors.contains(&Coord { x: 4, y: 5 }));
+        assert!(neighbors.contains(&Coord { x: 2, y: 5 }));
+        assert!(neighbors.contains(&Coord { x: 3, y: 6 }));
+        assert!(neighbors.contains(&Coord { x: 3, y: 4 }));
+    }
+
+    #[test]
+    fn coords_in_rect_row_major() {
+        let coords: Vec<Coord> = coords_in_rect(Coord::new(1, -1), Coord::new(2, 1)).collect();
+        assert_eq!(coords, vec![
+            Coord::new(1, -1), Coord::new(2, -1),
+            Coord::new(1, 0), Coord::new(2, 0),
+            Coord::new(1, 1), Coord::new(2, 1),
+        ]);
+        assert_eq!(coords_in_rect(Coord::origin(), Coord::new(-1, 3)).count(), 0);
+    }
+
+    #[test]
+    fn const_arithmetic() {
+        const CORNER: Coord = Coord::new(1, 1).add_vec(Vec2D::new(1, 0).add(Vec2D::new(0, 1)));
+        assert_eq!(CORNER, Coord::new(2, 2));
+        assert_eq!(CORNER, Coord::new(1, 1) + (Vec2D::new(1, 0) + Vec2D::new(0, 1)));
+    }
+
+    #[test]
+    fn cardinals() {
+        for (i, a) in CARDINALS.iter().enumerate() {
+            for b in &CARDINALS[i + 1..] {
+                assert_ne!(a, b);
+            }
+        }
+        assert_eq!(CARDINALS.into_iter().fold(Vec2D::zero(), Vec2D::add), Vec2D::zero());
+        assert_eq!(Coord::origin() + Vec2D::DOWN, Coord::new(0, 1));
+    }
+
+    #[test]
+    fn neighbors4_in() {
+        let (min, max) = (Coord::origin(), Coord::new(5, 9));
+        let corner = Coord::origin().neighbors4_in(min, max);
+        assert_eq!(corner.len(), 2);
+        assert!(corner.contains(&Coord::new(1, 0)) && corner.contains(&Coord::new(0, 1)));
+        assert_eq!(Coord::new(5, 4).neighbors4_in(min, max).len(), 3);
+        assert_eq!(Coord::new(2, 4).neighbors4_in(min, max).len(), 4);
+        // the bounds of an empty rectangle hold nothing
+        assert!(Coord::origin().neighbors4_in(Coord::origin(), Coord::new(-1, -1)).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn clamp_inverted() {
+        Coord::origin().clamp(Coord::origin(), Coord::new(-1, -1));
+    }
 }
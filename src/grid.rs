@@ -0,0 +1,116 @@
+use alloc::{vec, vec::Vec};
+
+use crate::cell_shape::{CellShape, Tile};
+use crate::coord::Coord;
+
+/// A dense width by height grid with a value for every tile, stored row-major.
+/// Unlike `CellShape`, looking up a tile does not need to scan.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    /// A grid with every tile set to `value`
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T> {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    // Where the tile is stored in `cells`, if it is inside the grid
+    fn position(&self, coord: Coord) -> Option<usize> {
+        let (x, y) = (usize::try_from(coord.x).ok()?, usize::try_from(coord.y).ok()?);
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+    /// The value at the tile, or None when it is outside the grid
+    pub fn get(&self, coord: Coord) -> Option<&T> {
+        self.position(coord).map(|i| &self.cells[i])
+    }
+    /// Replaces the value at the tile.
+    /// Panics when the tile is outside the grid.
+    pub fn set(&mut self, coord: Coord, value: T) {
+        let Some(i) = self.position(coord) else {
+            panic!("{} is outside the {}x{} grid", coord, self.width, self.height);
+        };
+        self.cells[i] = value;
+    }
+    /// Every tile with its value, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> + '_ {
+        self.cells.iter().enumerate().map(|(i, value)| {
+            let coord = Coord::new((i % self.width) as isize, (i / self.width) as isize);
+            (coord, value)
+        })
+    }
+}
+
+impl Grid<Tile> {
+    /// The shape made of the filled tiles
+    pub fn to_shape(&self) -> CellShape {
+        let coords = self
+            .iter()
+            .filter(|(_, tile)| tile.is_filled())
+            .map(|(coord, _)| coord)
+            .collect();
+        CellShape::from_coordinate_list(coords)
+    }
+}
+
+impl From<&CellShape> for Grid<Tile> {
+    /// Covers the shape's bounding box
+    fn from(shape: &CellShape) -> Grid<Tile> {
+        let (width, height) = shape.bounding_box();
+        let mut grid = Grid::new(width as usize, height as usize, Tile::Empty);
+        for coord in shape.filled_tiles() {
+            grid.set(coord, Tile::Filled);
+        }
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Tile::*;
+
+    #[test]
+    fn get_and_set() {
+        let mut grid = Grid::new(3, 2, 0);
+        grid.set(Coord::new(2, 1), 7);
+        assert_eq!(grid.get(Coord::new(2, 1)), Some(&7));
+        assert_eq!(grid.get(Coord::new(1, 1)), Some(&0));
+        assert_eq!(grid.get(Coord::new(3, 0)), None);
+        assert_eq!(grid.get(Coord::new(0, -1)), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_outside() {
+        Grid::new(3, 2, 0).set(Coord::new(0, 2), 1);
+    }
+
+    #[test]
+    fn cell_shape_round_trip() {
+        let shape = CellShape::from_2darray([
+            [Filled, Filled, Empty],
+            [Empty, Filled, Filled],
+        ]);
+        let grid = Grid::from(&shape);
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(Coord::new(0, 1)), Some(&Empty));
+        assert_eq!(grid.get(Coord::new(2, 1)), Some(&Filled));
+        assert_eq!(grid.to_shape(), shape);
+    }
+}
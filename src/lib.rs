@@ -0,0 +1,16 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+pub mod pentomino;
+pub mod tromino;
+pub mod cell_shape;
+pub mod transform;
+pub mod coord;
+pub mod grid;
+#[cfg(feature = "std")]
+pub mod solver;
+#[cfg(feature = "std")]
+pub mod animate;
+#[cfg(feature = "std")]
+pub mod svg;
@@ -1,7 +1,9 @@
-mod pentomino;
-mod cell_shape;
-mod transform;
-mod coord;
+use std::io::Write;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use pentominoes::{animate, cell_shape::{render_row, CellShape}, coord::Coord, pentomino, solver, svg};
 
 use clap::{
     Subcommand,
@@ -18,7 +20,43 @@ struct Cli {
 
 #[derive(Subcommand)]
 enum Command {
-    DescribePentominoes
+    DescribePentominoes,
+    /// Solve a rectangular board and print the solution
+    Solve {
+        width: usize,
+        height: usize,
+        /// Print the placements as JSON instead of drawing the board
+        #[arg(long)]
+        json: bool,
+        /// Print every solution as it is found, separated by blank lines
+        #[arg(long)]
+        all: bool,
+        /// Stop after this many solutions, when printing all of them
+        #[arg(long, requires = "all")]
+        limit: Option<usize>,
+        /// Give up after this many seconds, keeping whatever was found by then
+        #[arg(long, value_parser = parse_timeout)]
+        timeout: Option<Duration>,
+    },
+    /// List the rectangles the 12 pentominoes can tile
+    Rectangles,
+    /// Print an SVG sheet of every pentomino orientation
+    Sheet,
+    /// Check that a solution exactly tiles a board, exiting with an error if it doesn't
+    Verify {
+        /// A text file marking the board's tiles with `#`
+        board: PathBuf,
+        /// A text file labeling each tile with the letter of the pentomino covering it, lined up with the board
+        solution: PathBuf,
+    },
+    /// Watch the solver fill in a rectangular board
+    Animate {
+        width: usize,
+        height: usize,
+        /// Frames drawn per second, or 0 to draw as fast as possible
+        #[arg(long, default_value_t = 30)]
+        fps: u32,
+    },
 }
 
 fn main() {
@@ -27,6 +65,49 @@ fn main() {
         Command::DescribePentominoes => {
             describe_pentominoes();
         }
+        Command::Solve { width, height, json, all: true, limit, timeout } => {
+            let board = CellShape::rectangle(width, height);
+            let config = solver_config(timeout);
+            let (printed, stats) = print_all_solutions(&board, &config, json, limit.unwrap_or(usize::MAX));
+            if stats.timed_out {
+                eprintln!("timed out after {printed} solutions");
+            }
+        }
+        Command::Solve { width, height, json, all: false, timeout, .. } => {
+            let board = CellShape::rectangle(width, height);
+            let config = solver_config(timeout);
+            match solver::solve_with_stats(&board, &pentomino::PENTOMINOES, &config) {
+                (Some(placements), _) if json => println!("{}", solver::solution_to_json(&placements)),
+                (Some(placements), _) => print!("{}", solver::render_solution(&board, &placements)),
+                (None, stats) if stats.timed_out => println!("timed out"),
+                (None, _) => println!("no solution"),
+            }
+        }
+        Command::Rectangles => {
+            for (width, height) in solver::solvable_rectangles() {
+                println!("{width}x{height}");
+            }
+        }
+        Command::Sheet => {
+            print!("{}", svg::orientation_sheet_svg());
+        }
+        Command::Verify { board, solution } => {
+            match verify(&board, &solution) {
+                Ok(()) => println!("valid"),
+                Err(err) => {
+                    eprintln!("invalid: {err}");
+                    std::process::exit(1);
+                }
+            }
+        }
+        Command::Animate { width, height, fps } => {
+            let board = CellShape::rectangle(width, height);
+            match animate::animate(&board, fps, &mut std::io::stdout()) {
+                Ok(Some(_)) => {}
+                Ok(None) => println!("no solution"),
+                Err(err) => eprintln!("{err}"),
+            }
+        }
     }
 }
 
@@ -35,14 +116,79 @@ fn describe_pentominoes() {
         .iter()
         .for_each(|p| {
 
-            let shapes = p.shapes();
+            let shapes = p.shapes_sorted();
 
             println!("================================");
             println!(" {p:?} has {} distinct orientations", shapes.len());
-            for (i, s) in shapes.iter().enumerate() {
-                println!("{}. ", i + 1);
-                s.print_out();
+            for line in render_row(&shapes, 2).lines() {
+                println!("  {}", line);
             }
     });
 
 }
+
+// Reads a number of seconds, which has to be finite and not negative
+fn parse_timeout(arg: &str) -> Result<Duration, String> {
+    let seconds: f64 = arg.parse().map_err(|_| format!("{arg} is not a number of seconds"))?;
+    Duration::try_from_secs_f64(seconds).map_err(|_| format!("{arg} is not a finite, non-negative number of seconds"))
+}
+
+// How the solve command searches, giving up after `timeout` if there is one
+fn solver_config(timeout: Option<Duration>) -> solver::SolverConfig {
+    solver::SolverConfig {
+        memoize: true,
+        deadline: timeout.map(|timeout| Instant::now() + timeout),
+        ..solver::SolverConfig::default()
+    }
+}
+
+// Streams up to `limit` solutions to stdout as the search finds them,
+// returning how many were printed along with how the search went
+fn print_all_solutions(board: &CellShape, config: &solver::SolverConfig, json: bool, limit: usize) -> (usize, solver::SearchStats) {
+    if limit == 0 {
+        return (0, solver::SearchStats::default());
+    }
+    let mut out = std::io::stdout().lock();
+    let mut printed = 0;
+    let stats = solver::for_each_solution_with_config(board, &pentomino::PENTOMINOES, config, |placements| {
+        let result = if json {
+            writeln!(out, "{}", solver::solution_to_json(placements))
+        } else {
+            let separator = if printed == 0 { "" } else { "\n" };
+            write!(out, "{separator}{}", solver::render_solution(board, placements))
+        };
+        printed += 1;
+        // stop once the limit is reached, or the reader has gone away
+        if result.is_err() || printed == limit {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    });
+    (printed, stats)
+}
+
+// Loads the board and the solution, checking that the solution tiles the board
+fn verify(board_path: &Path, solution_path: &Path) -> Result<(), String> {
+    let read = |path: &Path| {
+        std::fs::read_to_string(path).map_err(|err| format!("can't read {}: {}", path.display(), err))
+    };
+    let mut tiles: Vec<Coord> = read(board_path)?
+        .lines()
+        .enumerate()
+        .flat_map(|(y, line)| line
+            .chars()
+            .enumerate()
+            .filter(|&(_, ch)| ch == '#')
+            .map(move |(x, _)| Coord::new(x as isize, y as isize)))
+        .collect();
+    // shapes are normalized, so the placements have to move along with the board
+    let shift = CellShape::shift_into_positive(&mut tiles);
+    let board = CellShape::from_coordinate_list(tiles);
+
+    let mut placements = solver::parse_solution(&read(solution_path)?)?;
+    for placement in &mut placements {
+        placement.offset = placement.offset + shift;
+    }
+    solver::validate_solution(&board, &placements)
+}
@@ -1,9 +1,13 @@
+use alloc::{format, string::String, vec::Vec};
+#[cfg(feature = "std")]
 use std::collections::HashSet;
 
 use crate::cell_shape::{Tile, CellShape};
 use crate::transform::{Transform, RIGID_SYMMETRIES};
+#[cfg(feature = "std")]
+use crate::transform::ROTATIONS;
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Pentomino {
     F,
     I,
@@ -36,13 +40,104 @@ pub const PENTOMINOES: [Pentomino; 12] = [
 
 impl Pentomino {
     /// Returns all possible orientations for this pentamino 
+    #[cfg(feature = "std")]
     pub fn shapes(self) -> HashSet<CellShape> {
+        create_all_orientations(self.representative(), RIGID_SYMMETRIES.as_slice())
+    }
+    /// Returns the orientations reachable without flipping the pentamino over
+    #[cfg(feature = "std")]
+    pub fn one_sided_shapes(self) -> HashSet<CellShape> {
+        create_all_orientations(self.representative(), ROTATIONS.as_slice())
+    }
+    /// How many orientations can only be reached by flipping the pentomino over.
+    /// This is zero exactly for the achiral pieces, which are their own mirror images
+    #[cfg(feature = "std")]
+    pub fn reflection_distinct_count(self) -> usize {
+        self.shapes().len() - self.one_sided_shapes().len()
+    }
+    /// Returns all possible orientations for this pentamino, sorted by their tiles so the order is reproducible
+    #[cfg(feature = "std")]
+    pub fn shapes_sorted(self) -> Vec<CellShape> {
+        let mut shapes: Vec<CellShape> = self.shapes().into_iter().collect();
+        shapes.sort();
+        shapes
+    }
+    /// The rigid symmetries that map this pentomino onto itself, always including the identity
+    pub fn symmetry_group(self) -> Vec<Transform> {
+        let rep = self.representative();
+        RIGID_SYMMETRIES
+            .iter()
+            .copied()
+            .filter(|t| t.transform_shape(rep.clone()) == rep)
+            .collect()
+    }
+    /// Names the kind of symmetry this pentomino has, by the size of its symmetry group
+    pub fn symmetry_type(self) -> &'static str {
+        match self.symmetry_group().len() {
+            8 => "full (D4)",
+            4 => "two mirror axes (D2)",
+            2 if self.symmetry_group().contains(&Transform::rotate180()) => "half turn (C2)",
+            2 => "one mirror axis (D1)",
+            _ => "asymmetric",
+        }
+    }
+    /// The letter naming this pentomino
+    pub fn letter(self) -> char {
+        match self {
+            Pentomino::F => 'F',
+            Pentomino::I => 'I',
+            Pentomino::L => 'L',
+            Pentomino::N => 'N',
+            Pentomino::P => 'P',
+            Pentomino::T => 'T',
+            Pentomino::U => 'U',
+            Pentomino::V => 'V',
+            Pentomino::W => 'W',
+            Pentomino::X => 'X',
+            Pentomino::Y => 'Y',
+            Pentomino::Z => 'Z',
+        }
+    }
+    /// A fill color for drawing this pentomino, as a CSS hex color
+    pub const fn color(self) -> &'static str {
+        match self {
+            Pentomino::F => "#e6194b",
+            Pentomino::I => "#3cb44b",
+            Pentomino::L => "#ffe119",
+            Pentomino::N => "#4363d8",
+            Pentomino::P => "#f58231",
+            Pentomino::T => "#911eb4",
+            Pentomino::U => "#42d4f4",
+            Pentomino::V => "#f032e6",
+            Pentomino::W => "#bfef45",
+            Pentomino::X => "#fabed4",
+            Pentomino::Y => "#469990",
+            Pentomino::Z => "#9a6324",
+        }
+    }
+    /// The pentomino named by the letter, if any
+    pub fn from_letter(letter: char) -> Option<Pentomino> {
+        PENTOMINOES.iter().copied().find(|p| p.letter() == letter)
+    }
+    /// Returns the letter, followed by the bounding box and bitmask of the representative shape
+    pub fn describe(self) -> String {
+        let rep = self.representative();
+        let (width, height) = rep.bounding_box();
+        let bits = rep.to_u64().unwrap();
+        format!("{self:?} {width}x{height}:{bits:#x}")
+    }
+    /// The orientation every other orientation of this pentomino is generated from,
+    /// such as for drawing a palette of pieces
+    pub fn default_shape(self) -> CellShape {
+        self.representative()
+    }
+    /// Get a representative shape for the pentamino
+    pub(crate) fn representative(self) -> CellShape {
         use Tile::{
             Empty as o,
             Filled as F,
         };
-        // Get a representative shape for the pentamino
-        let rep = match self {
+        match self {
             Pentomino::F => CellShape::from_2darray([
                 [o, F, F],
                 [F, F, o],
@@ -107,15 +202,100 @@ impl Pentomino {
                 [o, F, o],
                 [o, F, F],
             ]),
-        };
-        create_all_orientations(rep, RIGID_SYMMETRIES.as_slice())
+        }
     }
 }
 
 // Creates all unique orientations of a given shape and transforms
-fn create_all_orientations(rep: CellShape, symmetries: &[Transform]) -> HashSet<CellShape> {
+#[cfg(feature = "std")]
+pub(crate) fn create_all_orientations(rep: CellShape, symmetries: &[Transform]) -> HashSet<CellShape> {
     symmetries
         .iter()
-        .map(|t| t.transform_shape(rep.clone()))
+        .map(|t| rep.map_coords(|c| t.transform_coord(c)))
         .collect()
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describe_starts_with_letter() {
+        for p in PENTOMINOES {
+            assert!(p.describe().starts_with(p.letter()));
+        }
+        assert_eq!(Pentomino::I.describe(), "I 1x5:0x1f");
+    }
+
+    #[test]
+    fn shapes_sorted_is_stable() {
+        for p in PENTOMINOES {
+            let first = p.shapes_sorted();
+            let second = p.shapes_sorted();
+            assert_eq!(first, second);
+            assert_eq!(first.len(), p.shapes().len());
+        }
+    }
+
+    #[test]
+    fn letter_round_trip() {
+        for p in PENTOMINOES {
+            assert_eq!(Pentomino::from_letter(p.letter()), Some(p));
+        }
+        assert_eq!(Pentomino::from_letter('A'), None);
+    }
+
+    #[test]
+    fn one_sided_shapes() {
+        // chiral pieces lose their mirror images
+        assert_eq!(Pentomino::F.shapes().len(), 8);
+        assert_eq!(Pentomino::F.one_sided_shapes().len(), 4);
+        // achiral pieces are their own mirror images
+        assert_eq!(Pentomino::T.shapes().len(), 4);
+        assert_eq!(Pentomino::T.one_sided_shapes().len(), 4);
+        assert_eq!(Pentomino::X.one_sided_shapes().len(), 1);
+    }
+
+    #[test]
+    fn symmetry_types() {
+        assert_eq!(Pentomino::X.symmetry_type(), "full (D4)");
+        assert_eq!(Pentomino::I.symmetry_type(), "two mirror axes (D2)");
+        assert_eq!(Pentomino::Z.symmetry_type(), "half turn (C2)");
+        assert_eq!(Pentomino::T.symmetry_type(), "one mirror axis (D1)");
+        assert_eq!(Pentomino::F.symmetry_type(), "asymmetric");
+        for p in PENTOMINOES {
+            // orbit-stabilizer: the orientations and the symmetries split the 8 rigid motions between them
+            assert_eq!(p.symmetry_group().len() * p.shapes().len(), 8);
+        }
+    }
+
+    #[test]
+    fn default_shape_is_connected() {
+        for p in PENTOMINOES {
+            let shape = p.default_shape();
+            assert_eq!(shape.size(), 5);
+            let mut reached = vec![shape.filled_tiles().next().unwrap()];
+            let mut i = 0;
+            while i < reached.len() {
+                for n in reached[i].neighbors4() {
+                    if shape[n].is_filled() && !reached.contains(&n) {
+                        reached.push(n);
+                    }
+                }
+                i += 1;
+            }
+            assert_eq!(reached.len(), 5, "{:?} is not connected", p);
+        }
+    }
+
+    #[test]
+    fn reflection_distinct_count() {
+        assert_eq!(Pentomino::F.reflection_distinct_count(), 4);
+        assert_eq!(Pentomino::I.reflection_distinct_count(), 0);
+        for p in PENTOMINOES {
+            let chiral = "FLNPYZ".contains(p.letter());
+            assert_eq!(p.reflection_distinct_count() > 0, chiral, "{:?}", p);
+        }
+    }
+}
@@ -0,0 +1,2089 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::ControlFlow;
+use std::time::Instant;
+
+use crate::cell_shape::CellShape;
+use crate::coord::{Coord, Vec2D};
+use crate::grid::Grid;
+use crate::pentomino::{Pentomino, PENTOMINOES};
+use crate::transform::{Transform, RIGID_SYMMETRIES, ROTATIONS};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// A pentomino placed onto a board.
+/// The piece's representative is transformed, then its local origin is moved to `offset`.
+pub struct Placement {
+    pub piece: Pentomino,
+    pub transform: Transform,
+    pub offset: Vec2D,
+}
+
+impl Placement {
+    /// The oriented shape of the piece, in its local coordinate system
+    pub fn shape(&self) -> CellShape {
+        self.transform.transform_shape(self.piece.representative())
+    }
+    /// The board coordinates covered by this placement
+    pub fn cells(&self) -> Vec<Coord> {
+        self.shape().translate_to(Coord::origin() + self.offset)
+    }
+}
+
+/// The pentominoes none of the placements use, in PENTOMINOES order
+pub fn unused_pieces(placements: &[Placement]) -> Vec<Pentomino> {
+    PENTOMINOES
+        .into_iter()
+        .filter(|&piece| placements.iter().all(|p| p.piece != piece))
+        .collect()
+}
+
+/// Checks that the placements tile the board exactly.
+/// Each piece may be used at most once, no two placements may overlap, nothing may hang off the board,
+/// and every cell of the board must be covered.
+pub fn validate_solution(board: &CellShape, placements: &[Placement]) -> Result<(), String> {
+    let mut used_pieces = HashSet::new();
+    let mut covered = HashSet::new();
+
+    for placement in placements {
+        if !used_pieces.insert(placement.piece) {
+            return Err(format!("{:?} is used more than once", placement.piece));
+        }
+        for cell in placement.cells() {
+            if board[cell].is_empty() {
+                return Err(format!("{:?} covers {}, which is outside the board", placement.piece, cell));
+            }
+            if !covered.insert(cell) {
+                return Err(format!("{:?} overlaps another piece at {}", placement.piece, cell));
+            }
+        }
+    }
+
+    if let Some(cell) = board.filled_tiles().find(|c| !covered.contains(c)) {
+        return Err(format!("board cell {} is not covered", cell));
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Options for the backtracking search
+pub struct SolverConfig {
+    /// Whether pieces may be flipped over, or only rotated
+    pub allow_reflections: bool,
+    /// Whether to remember the partly covered boards that turned out to have no solution,
+    /// so the search can skip them when another order of placements reaches them again.
+    /// This trades memory for time, which pays off when a solution is hard to find.
+    pub memoize: bool,
+    /// Shuffles the order placements are tried in, so different seeds may find different solutions first.
+    /// The same seed always searches in the same order, and no seed keeps the sorted order.
+    pub seed: Option<u64>,
+    /// Whether a solution has to use every piece. Otherwise each piece is used at most once,
+    /// so a board smaller than the pieces together leaves some of them out
+    pub require_all_pieces: bool,
+    /// When to give up. A search that runs past it stops as if it had found every solution,
+    /// so anything not found by then is left out
+    pub deadline: Option<Instant>,
+}
+
+impl Default for SolverConfig {
+    fn default() -> SolverConfig {
+        SolverConfig {
+            allow_reflections: true,
+            memoize: false,
+            seed: None,
+            require_all_pieces: false,
+            deadline: None,
+        }
+    }
+}
+
+/// Counters describing how much work a search did
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct SearchStats {
+    /// Partly covered boards the search branched from, or found to be solved
+    pub nodes: u64,
+    /// Partly covered boards skipped, because they were already known to have no solution
+    /// or have an open region the pieces can't add up to
+    pub pruned: u64,
+    pub solutions: u64,
+    /// Whether the search gave up at the configured deadline, leaving some of the board unsearched
+    pub timed_out: bool,
+}
+
+/// Tiles the board using each of the twelve pentominoes at most once
+pub fn solve_region(board: &CellShape) -> Option<Vec<Placement>> {
+    solve_with_pieces(board, &PENTOMINOES)
+}
+
+/// Tiles the board using each of the given pieces at most once
+pub fn solve_with_pieces(board: &CellShape, pieces: &[Pentomino]) -> Option<Vec<Placement>> {
+    let config = SolverConfig {
+        memoize: true,
+        ..SolverConfig::default()
+    };
+    solve_with_config(board, pieces, &config)
+}
+
+/// Tiles the board using each of the given pieces at most once, searching as configured
+pub fn solve_with_config(board: &CellShape, pieces: &[Pentomino], config: &SolverConfig) -> Option<Vec<Placement>> {
+    solve_with_stats(board, pieces, config).0
+}
+
+/// Like `solve_with_config`, also reporting how much work the search did
+pub fn solve_with_stats(board: &CellShape, pieces: &[Pentomino], config: &SolverConfig) -> (Option<Vec<Placement>>, SearchStats) {
+    let Some(mut search) = Search::new(board, pieces, config) else {
+        return (None, SearchStats::default());
+    };
+    let mut solution = None;
+    let _ = search.run(&mut |placements: &[Placement]| {
+        solution = Some(placements.to_vec());
+        ControlFlow::Break(())
+    });
+    (solution, search.stats)
+}
+
+/// Runs the backtracking search, calling `on_solution` with every tiling of the board found.
+/// Each of the given pieces is used at most once. The search stops early if `on_solution` breaks.
+pub fn for_each_solution<F>(board: &CellShape, pieces: &[Pentomino], on_solution: F)
+where
+    F: FnMut(&[Placement]) -> ControlFlow<()>,
+{
+    for_each_solution_with_config(board, pieces, &SolverConfig::default(), on_solution);
+}
+
+/// Like `for_each_solution`, searching as configured, and reporting how much work the search did
+pub fn for_each_solution_with_config<F>(board: &CellShape, pieces: &[Pentomino], config: &SolverConfig, mut on_solution: F) -> SearchStats
+where
+    F: FnMut(&[Placement]) -> ControlFlow<()>,
+{
+    let Some(mut search) = Search::new(board, pieces, config) else {
+        return SearchStats::default();
+    };
+    let _ = search.run(&mut on_solution);
+    search.stats
+}
+
+/// Renders the board with each tile labeled by the letter of the piece covering it.
+/// Uncovered board tiles are drawn as `.` and tiles off the board as spaces.
+pub fn render_solution(board: &CellShape, placements: &[Placement]) -> String {
+    let labels = label_grid(board, placements);
+    let width = labels.width() as isize;
+    let mut out = String::new();
+    for (c, label) in labels.iter() {
+        let ch = match label {
+            Some(piece) => piece.letter(),
+            None if board[c].is_filled() => '.',
+            None => ' ',
+        };
+        out.push(ch);
+        if c.x == width - 1 {
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// The piece covering each tile of the board's bounding box, as rows indexed by `[y][x]`.
+/// Tiles that are uncovered or off the board are None
+pub fn solution_to_grid(board: &CellShape, placements: &[Placement]) -> Vec<Vec<Option<Pentomino>>> {
+    let labels = label_grid(board, placements);
+    let mut rows = vec![Vec::with_capacity(labels.width()); labels.height()];
+    for (c, &label) in labels.iter() {
+        rows[c.y as usize].push(label.filter(|_| board[c].is_filled()));
+    }
+    rows
+}
+
+// The piece covering each tile of the board's bounding box, ignoring tiles off the box
+fn label_grid(board: &CellShape, placements: &[Placement]) -> Grid<Option<Pentomino>> {
+    let (width, height) = board.bounding_box();
+    let mut labels = Grid::new(width as usize, height as usize, None);
+    for placement in placements {
+        for c in placement.cells() {
+            if labels.get(c).is_some() {
+                labels.set(c, Some(placement.piece));
+            }
+        }
+    }
+    labels
+}
+
+// How an edge between two tiles is drawn by `render_solution_boxed`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Edge {
+    None = 0,
+    Light = 1,
+    Heavy = 2,
+}
+
+/// Renders the solution with box-drawing lines, each tile two characters wide.
+/// Heavy lines outline every piece and the board; light lines separate the tiles within a piece.
+/// Uncovered board tiles are drawn as `..`.
+pub fn render_solution_boxed(board: &CellShape, placements: &[Placement]) -> String {
+    let labels = label_grid(board, placements);
+    let (width, height) = (labels.width() as isize, labels.height() as isize);
+    // the piece covering an on board tile, with None for tiles off the board
+    let label = |c: Coord| board[c].is_filled().then(|| labels.get(c).copied().flatten());
+    let edge = |a: Coord, b: Coord| match (label(a), label(b)) {
+        (None, None) => Edge::None,
+        (a, b) if a == b => Edge::Light,
+        _ => Edge::Heavy,
+    };
+    // the edge left of tile c, and the edge above it
+    let vertical = |c: Coord| edge(c + Vec2D::LEFT, c);
+    let horizontal = |c: Coord| edge(c + Vec2D::UP, c);
+
+    let mut out = String::new();
+    for y in 0..=height {
+        for x in 0..=width {
+            let c = Coord::new(x, y);
+            let arms = [
+                vertical(c + Vec2D::UP),
+                vertical(c),
+                horizontal(c + Vec2D::LEFT),
+                horizontal(c),
+            ];
+            out.push(junction(arms));
+            if x < width {
+                let line = match horizontal(c) {
+                    Edge::None => ' ',
+                    Edge::Light => '─',
+                    Edge::Heavy => '━',
+                };
+                out.push(line);
+                out.push(line);
+            }
+        }
+        out.push('\n');
+        if y == height {
+            break;
+        }
+        for x in 0..=width {
+            let c = Coord::new(x, y);
+            out.push(match vertical(c) {
+                Edge::None => ' ',
+                Edge::Light => '│',
+                Edge::Heavy => '┃',
+            });
+            if x < width {
+                out.push_str(match label(c) {
+                    Some(None) => "..",
+                    _ => "  ",
+                });
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+// The box-drawing characters joining edges at a corner, indexed by the edges above, below, left and right
+// of it as the digits of a base 3 number, so the last one is heavy in every direction
+const JUNCTIONS: [char; 81] = [
+    ' ', '╶', '╺', '╴', '─', '╼', '╸', '╾', '━',
+    '╷', '┌', '┍', '┐', '┬', '┮', '┑', '┭', '┯',
+    '╻', '┎', '┏', '┒', '┰', '┲', '┓', '┱', '┳',
+    '╵', '└', '┕', '┘', '┴', '┶', '┙', '┵', '┷',
+    '│', '├', '┝', '┤', '┼', '┾', '┥', '┽', '┿',
+    '╽', '┟', '┢', '┧', '╁', '╆', '┪', '╅', '╈',
+    '╹', '┖', '┗', '┚', '┸', '┺', '┛', '┹', '┻',
+    '╿', '┞', '┡', '┦', '╀', '╄', '┩', '╃', '╇',
+    '┃', '┠', '┣', '┨', '╂', '╊', '┫', '╉', '╋',
+];
+
+fn junction([up, down, left, right]: [Edge; 4]) -> char {
+    JUNCTIONS[up as usize * 27 + down as usize * 9 + left as usize * 3 + right as usize]
+}
+
+/// Writes the placements as a JSON array of objects like `{"piece":"F","transform":"rotate90","offset":[2,3]}`
+pub fn solution_to_json(placements: &[Placement]) -> String {
+    let objects: Vec<String> = placements
+        .iter()
+        .map(|p| {
+            format!(
+                r#"{{"piece":"{}","transform":"{}","offset":[{},{}]}}"#,
+                p.piece.letter(),
+                p.transform.name(),
+                p.offset.x,
+                p.offset.y
+            )
+        })
+        .collect();
+    format!("[{}]", objects.join(","))
+}
+
+/// Writes every tiling of a width by height rectangle as CSV, one line per solution as it is found.
+/// A header line names the 12 columns by piece letter, and each field is the piece's offset and transform,
+/// like `2 3 rotate90`, or empty when the piece isn't used.
+pub fn solutions_to_csv(width: usize, height: usize, out: &mut impl std::io::Write) -> std::io::Result<()> {
+    let letters: Vec<String> = PENTOMINOES.iter().map(|p| p.letter().to_string()).collect();
+    writeln!(out, "{}", letters.join(","))?;
+    let mut result = Ok(());
+    for_each_solution(&CellShape::rectangle(width, height), &PENTOMINOES, |placements| {
+        let fields: Vec<String> = PENTOMINOES
+            .iter()
+            .map(|&piece| match placements.iter().find(|p| p.piece == piece) {
+                Some(p) => format!("{} {} {}", p.offset.x, p.offset.y, p.transform.name()),
+                None => String::new(),
+            })
+            .collect();
+        result = writeln!(out, "{}", fields.join(","));
+        if result.is_ok() {
+            ControlFlow::Continue(())
+        } else {
+            ControlFlow::Break(())
+        }
+    });
+    result
+}
+
+/// Every solution of the width by height rectangle, sorted by their rendered boards
+/// so the order is reproducible no matter how the search happens to run.
+pub fn solutions_sorted(width: usize, height: usize) -> Vec<Vec<Placement>> {
+    let board = CellShape::rectangle(width, height);
+    let mut solutions = Vec::new();
+    for_each_solution(&board, &PENTOMINOES, |placements| {
+        solutions.push((render_solution(&board, placements), placements.to_vec()));
+        ControlFlow::Continue(())
+    });
+    solutions.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+    solutions.into_iter().map(|(_, placements)| placements).collect()
+}
+
+/// Searches every solution of the board, returning one with the smallest total placement cost
+/// along with that cost. Ties are broken in favor of the solution found first.
+pub fn solve_min_cost(board: &CellShape, cost: impl Fn(&Placement) -> i64) -> Option<(i64, Vec<Placement>)> {
+    let mut best: Option<(i64, Vec<Placement>)> = None;
+    for_each_solution(board, &PENTOMINOES, |placements| {
+        let total = placements.iter().map(&cost).sum();
+        if best.as_ref().is_none_or(|(best_total, _)| total < *best_total) {
+            best = Some((total, placements.to_vec()));
+        }
+        ControlFlow::Continue(())
+    });
+    best
+}
+
+/// Reads a board where every tile is labeled by a pentomino letter, as produced by `render_solution`.
+/// Spaces and `.` are skipped. Each connected group of the same letter must be an orientation of that pentomino.
+pub fn parse_solution(board_ascii: &str) -> Result<Vec<Placement>, String> {
+    let mut labels = HashMap::new();
+    for (y, line) in board_ascii.lines().enumerate() {
+        for (x, ch) in line.chars().enumerate() {
+            if ch == ' ' || ch == '.' {
+                continue;
+            }
+            labels.insert(Coord::new(x as isize, y as isize), ch);
+        }
+    }
+
+    let mut tiles: Vec<Coord> = labels.keys().copied().collect();
+    tiles.sort_by_key(|c| (c.y, c.x));
+
+    let mut seen = HashSet::new();
+    let mut placements = Vec::new();
+    for start in tiles {
+        if seen.contains(&start) {
+            continue;
+        }
+        let letter = labels[&start];
+        let piece = Pentomino::from_letter(letter)
+            .ok_or_else(|| format!("{:?} at {} is not a pentomino", letter, start))?;
+
+        // flood fill the tiles with the same letter
+        let mut group = vec![start];
+        let mut stack = vec![start];
+        seen.insert(start);
+        while let Some(c) = stack.pop() {
+            for n in c.neighbors4() {
+                if labels.get(&n) == Some(&letter) && seen.insert(n) {
+                    group.push(n);
+                    stack.push(n);
+                }
+            }
+        }
+
+        let min = group.iter().copied().reduce(Coord::component_min).unwrap();
+        let shape = CellShape::from_coordinate_list(group);
+        let transform = orientations(piece)
+            .into_iter()
+            .find(|(_, s)| *s == shape)
+            .map(|(t, _)| t)
+            .ok_or_else(|| format!("the {} tiles at {} are not a {:?} pentomino", letter, start, piece))?;
+
+        placements.push(Placement {
+            piece,
+            transform,
+            offset: min - Coord::origin(),
+        });
+    }
+    Ok(placements)
+}
+
+/// Counts the tilings of the width by height rectangle by all 12 pentominoes.
+/// Reflections and rotations of a tiling are counted separately.
+pub fn count_solutions(width: usize, height: usize) -> u64 {
+    let mut count = 0;
+    for_each_solution(&CellShape::rectangle(width, height), &PENTOMINOES, |_| {
+        count += 1;
+        ControlFlow::Continue(())
+    });
+    count
+}
+
+/// Counts the tilings of the board by all 12 pentominoes, stopping once `cap` have been found.
+/// Returns `cap` when the board has at least that many, without searching for the rest.
+pub fn count_solutions_capped(board: &CellShape, cap: u64) -> u64 {
+    if cap == 0 {
+        return 0;
+    }
+    let mut count = 0;
+    for_each_solution(board, &PENTOMINOES, |_| {
+        count += 1;
+        if count == cap {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    });
+    count
+}
+
+/// Counts the tilings of a width by height rectangle by which piece covers its top left tile.
+/// Pieces that never cover the corner are left out.
+pub fn corner_piece_distribution(width: usize, height: usize) -> HashMap<Pentomino, u64> {
+    let mut counts = HashMap::new();
+    for_each_solution(&CellShape::rectangle(width, height), &PENTOMINOES, |placements| {
+        if let Some(p) = placements.iter().find(|p| p.cells().contains(&Coord::origin())) {
+            *counts.entry(p.piece).or_insert(0) += 1;
+        }
+        ControlFlow::Continue(())
+    });
+    counts
+}
+
+/// The rigid symmetries that map the board onto itself, always including the identity
+pub fn board_symmetries(board: &CellShape) -> Vec<Transform> {
+    RIGID_SYMMETRIES
+        .iter()
+        .copied()
+        .filter(|t| t.transform_shape(board.clone()) == *board)
+        .collect()
+}
+
+/// Counts the solutions of the board, treating two solutions as the same when one of the
+/// given board symmetries maps one onto the other.
+/// Each symmetry must map the board onto itself, and `board_symmetries` gives all of them.
+pub fn count_distinct_solutions(board: &CellShape, symmetries: &[Transform]) -> Result<u64, String> {
+    if let Some(t) = symmetries.iter().find(|t| t.transform_shape(board.clone()) != *board) {
+        return Err(format!("{:?} does not map the board onto itself", t));
+    }
+    let mut distinct = HashSet::new();
+    for_each_solution(board, &PENTOMINOES, |placements| {
+        let canonical = symmetries
+            .iter()
+            .map(|&t| labels_under(placements, t))
+            .chain(std::iter::once(labels_under(placements, Transform::identity())))
+            .min()
+            .unwrap();
+        distinct.insert(canonical);
+        ControlFlow::Continue(())
+    });
+    Ok(distinct.len() as u64)
+}
+
+/// Whether some rigid symmetry of the board maps solution `a` onto solution `b`,
+/// comparing which piece covers each tile the same way `count_distinct_solutions` does
+pub fn solutions_equivalent(board: &CellShape, a: &[Placement], b: &[Placement]) -> bool {
+    let target = labels_under(b, Transform::identity());
+    board_symmetries(board)
+        .into_iter()
+        .any(|t| labels_under(a, t) == target)
+}
+
+// The piece covering each board tile after transforming the solution, in sorted tile order
+fn labels_under(placements: &[Placement], t: Transform) -> Vec<Pentomino> {
+    let mut labels: Vec<(Coord, Pentomino)> = placements
+        .iter()
+        .flat_map(|p| p.cells().into_iter().map(move |c| (t.transform_coord(c), p.piece)))
+        .collect();
+    // sorting doesn't care about translation, so there's no need to normalize
+    labels.sort_by_key(|&(c, _)| (c.x, c.y));
+    labels.into_iter().map(|(_, p)| p).collect()
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// A step taken by the backtracking search
+pub enum SearchEvent<P = Placement> {
+    Place(P),
+    Remove(P),
+}
+
+/// Tiles the board like `solve_region`, reporting every placement made and undone along the way.
+/// Once a solution is found its placements are left in place, so replaying the events ends on the solution.
+pub fn solve_region_traced(board: &CellShape, on_event: impl FnMut(SearchEvent)) -> Option<Vec<Placement>> {
+    let mut solution = None;
+    if let Some(mut search) = Search::new(board, &PENTOMINOES, &SolverConfig::default()) {
+        search.trace = Some(Box::new(on_event));
+        let _ = search.run(&mut |placements: &[Placement]| {
+            solution = Some(placements.to_vec());
+            ControlFlow::Break(())
+        });
+    }
+    solution
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// A placement of an arbitrary piece, identified by its index in the piece list passed to `solve_mixed`
+pub struct MixedPlacement {
+    pub piece: usize,
+    pub transform: Transform,
+    pub offset: Vec2D,
+}
+
+impl MixedPlacement {
+    /// The board coordinates covered, given the shape of the piece that was placed
+    pub fn cells(&self, piece: &CellShape) -> Vec<Coord> {
+        self.transform
+            .transform_shape(piece.clone())
+            .translate_to(Coord::origin() + self.offset)
+    }
+}
+
+/// Tiles the board with arbitrary pieces, given along with the maximum number of times each may be used.
+/// Boards with more than 128 tiles are not supported.
+pub fn solve_mixed(board: &CellShape, pieces: &[(CellShape, usize)]) -> Option<Vec<MixedPlacement>> {
+    let search_pieces: Vec<SearchPiece> = pieces
+        .iter()
+        .map(|(shape, limit)| SearchPiece {
+            orientations: orientations_of(shape, &RIGID_SYMMETRIES),
+            limit: *limit,
+        })
+        .collect();
+    let mut search = Search::with_pieces(board, &search_pieces, |piece, transform, offset| MixedPlacement {
+        piece,
+        transform,
+        offset,
+    })?;
+    let mut solution = None;
+    let _ = search.run(&mut |placements: &[MixedPlacement]| {
+        solution = Some(placements.to_vec());
+        ControlFlow::Break(())
+    });
+    solution
+}
+
+/// The pairs of pentominoes, in any orientation, that together tile a 2x5 rectangle.
+/// A pentomino may pair with a second copy of itself. Each pair is listed once, with the smaller piece first.
+pub fn pairs_tiling_2x5() -> Vec<(Pentomino, Pentomino)> {
+    let board = CellShape::rectangle(5, 2);
+    let mut pairs = Vec::new();
+    for (i, &a) in PENTOMINOES.iter().enumerate() {
+        for &b in &PENTOMINOES[i..] {
+            let pieces = if a == b {
+                vec![(a.representative(), 2)]
+            } else {
+                vec![(a.representative(), 1), (b.representative(), 1)]
+            };
+            if solve_mixed(&board, &pieces).is_some() {
+                pairs.push((a, b));
+            }
+        }
+    }
+    pairs
+}
+
+/// Every legal placement of the pieces on the board, paired with the board indices it covers.
+/// These are the rows of the exact cover problem the solver works on, with the columns numbered by `cell_index`.
+pub fn placement_rows(board: &CellShape, pieces: &[Pentomino]) -> Vec<(Placement, Vec<usize>)> {
+    let index = board.cell_index();
+    let mut rows = Vec::new();
+    for &piece in pieces {
+        for (transform, shape) in orientations(piece) {
+            let anchor = shape.filled_tiles().next().unwrap();
+            for target in board.filled_tiles() {
+                let offset = target - anchor;
+                if let Some(covered) = covered_indices(&shape, offset, &index) {
+                    rows.push((Placement { piece, transform, offset }, covered));
+                }
+            }
+        }
+    }
+    rows
+}
+
+/// Every way to place `a` and `b` on the board without the two overlapping.
+/// The pairs are ordered, so when `a` and `b` are the same piece each arrangement shows up twice, once per order
+pub fn disjoint_placement_pairs(board: &CellShape, a: Pentomino, b: Pentomino) -> Vec<(Placement, Placement)> {
+    let (rows_a, rows_b) = (placement_rows(board, &[a]), placement_rows(board, &[b]));
+    let mut pairs = Vec::new();
+    for (placement_a, covered_a) in &rows_a {
+        for (placement_b, covered_b) in &rows_b {
+            if covered_a.iter().all(|i| !covered_b.contains(i)) {
+                pairs.push((*placement_a, *placement_b));
+            }
+        }
+    }
+    pairs
+}
+
+/// Finishes tiling the board around placements that are already fixed, using each remaining pentomino at most once.
+/// The solution starts with the prefilled placements. Returns None when they overlap, hang off the board,
+/// repeat a piece, or leave a part of the board that can't be tiled
+pub fn solve_with_prefilled(board: &CellShape, prefilled: &[Placement]) -> Option<Vec<Placement>> {
+    let mut solution = None;
+    for_each_completion(board, prefilled, |placements| {
+        solution = Some(placements.to_vec());
+        ControlFlow::Break(())
+    });
+    solution
+}
+
+/// Every tiling of a width by height rectangle where `piece` covers the top left tile.
+/// Fixing the corner piece first leaves a smaller search for the rest of the board,
+/// which only has to be run once for each way the piece can cover the corner.
+/// Each of those searches runs when the iterator reaches it, buffering just its own solutions
+pub fn boards_with_forced_corner(width: usize, height: usize, piece: Pentomino) -> impl Iterator<Item = Vec<Placement>> {
+    let board = CellShape::rectangle(width, height);
+    placements_covering(&board, piece, Coord::origin())
+        .into_iter()
+        .flat_map(move |forced| {
+            let mut solutions = Vec::new();
+            for_each_completion(&board, &[forced], |placements| {
+                solutions.push(placements.to_vec());
+                ControlFlow::Continue(())
+            });
+            solutions
+        })
+}
+
+// Calls `on_solution` with each tiling of the board that starts with the prefilled placements,
+// doing nothing when they overlap, hang off the board or repeat a piece
+fn for_each_completion<F>(board: &CellShape, prefilled: &[Placement], mut on_solution: F)
+where
+    F: FnMut(&[Placement]) -> ControlFlow<()>,
+{
+    let mut covered = HashSet::new();
+    for placement in prefilled {
+        for c in placement.cells() {
+            if board[c].is_empty() || !covered.insert(c) {
+                return;
+            }
+        }
+    }
+    let unused = unused_pieces(prefilled);
+    if unused.len() + prefilled.len() != PENTOMINOES.len() {
+        return;
+    }
+    let mut open: Vec<Coord> = board.filled_tiles().filter(|c| !covered.contains(c)).collect();
+    let shift = CellShape::shift_into_positive(&mut open);
+    let config = SolverConfig {
+        memoize: true,
+        ..SolverConfig::default()
+    };
+    let mut solution = prefilled.to_vec();
+    for_each_solution_with_config(&CellShape::from_coordinate_list(open), &unused, &config, |rest| {
+        solution.truncate(prefilled.len());
+        solution.extend(rest.iter().map(|p| Placement { offset: p.offset - shift, ..*p }));
+        on_solution(&solution)
+    });
+}
+
+/// Tiles a width by height rectangle around a frame of placements that only cover its border tiles,
+/// filling the rest with the remaining pentominoes. Fails when a frame placement reaches inside the border,
+/// otherwise returns None when the frame overlaps itself, repeats a piece, or can't be completed
+pub fn solve_framed(width: usize, height: usize, frame_pieces: &[Placement]) -> Result<Option<Vec<Placement>>, String> {
+    let (max_x, max_y) = (width as isize - 1, height as isize - 1);
+    let on_border = |c: Coord| {
+        (0..=max_x).contains(&c.x) && (0..=max_y).contains(&c.y)
+            && (c.x == 0 || c.y == 0 || c.x == max_x || c.y == max_y)
+    };
+    for placement in frame_pieces {
+        if let Some(c) = placement.cells().into_iter().find(|&c| !on_border(c)) {
+            return Err(format!("{:?} covers {}, which is not on the border", placement.piece, c));
+        }
+    }
+    Ok(solve_with_prefilled(&CellShape::rectangle(width, height), frame_pieces))
+}
+
+/// Makes a "fill in the rest" puzzle from a tiling of the width by height rectangle, found in the order
+/// picked by `seed`. Returns the board with `reveal` of the solution's placements, also picked by the seed,
+/// which the rest of that solution always completes. Returns None when the rectangle can't be tiled
+pub fn generate_puzzle(width: usize, height: usize, reveal: usize, seed: u64) -> Option<(CellShape, Vec<Placement>)> {
+    let board = CellShape::rectangle(width, height);
+    let config = SolverConfig {
+        memoize: true,
+        seed: Some(seed),
+        ..SolverConfig::default()
+    };
+    let mut givens = solve_with_config(&board, &PENTOMINOES, &config)?;
+    shuffle(&mut givens, seed);
+    givens.truncate(reveal);
+    Some((board, givens))
+}
+
+/// Counts the tilings of a width by height torus, where pieces wrap around from one edge to the opposite one.
+/// Solutions covering the same tiles with the same pieces are only counted once.
+pub fn count_solutions_torus(width: usize, height: usize) -> u64 {
+    let board = CellShape::rectangle(width, height);
+    let index = board.cell_index();
+    let (width, height) = (width as isize, height as isize);
+    let wrap = |c: Coord| index.get(&Coord::new(c.x.rem_euclid(width), c.y.rem_euclid(height))).copied();
+
+    let pieces: Vec<SearchPiece> = PENTOMINOES
+        .iter()
+        .map(|&p| SearchPiece::pentomino(p, &SolverConfig::default()))
+        .collect();
+    let Some(mut search) = Search::with_locator(&board, &pieces, wrap, None, |_, _, _| ()) else {
+        return 0;
+    };
+    let mut count = 0;
+    let _ = search.run(&mut |_: &[()]| {
+        count += 1;
+        ControlFlow::Continue(())
+    });
+    count
+}
+
+/// The width by height rectangles of area 60, with width at most height, that the 12 pentominoes can tile.
+/// Every factorization is searched, so 1x60 and 2x30 are checked and left out.
+pub fn solvable_rectangles() -> Vec<(usize, usize)> {
+    solve_all_rectangles_of_area(5 * PENTOMINOES.len())
+        .into_iter()
+        .filter_map(|(size, solution)| solution.map(|_| size))
+        .collect()
+}
+
+/// The width and height of a rectangle, with a tiling if one was found
+pub type RectangleAttempt = ((usize, usize), Option<Vec<Placement>>);
+
+/// Tries to tile every width by height rectangle of the given area, with width at most height,
+/// using each pentomino at most once. Areas that aren't a multiple of 5 can't be tiled,
+/// so their rectangles are listed without searching.
+pub fn solve_all_rectangles_of_area(area: usize) -> Vec<RectangleAttempt> {
+    (1..=area)
+        .take_while(|width| width * width <= area)
+        .filter(|width| area.is_multiple_of(*width))
+        .map(|width| (width, area / width))
+        .map(|(width, height)| {
+            let solution = if area.is_multiple_of(5) {
+                solve_region(&CellShape::rectangle(width, height))
+            } else {
+                None
+            };
+            ((width, height), solution)
+        })
+        .collect()
+}
+
+/// The board tile covered by the fewest placements of the available pieces, which is where the search branches next.
+/// Ties go to the first tile in sorted order.
+/// Returns None for an empty board, or one with more tiles than the pieces can cover.
+pub fn most_constrained_cell(board: &CellShape, available: &[Pentomino]) -> Option<Coord> {
+    let search = Search::new(board, available, &SolverConfig::default())?;
+    let (tile, _) = search.most_constrained()?;
+    Some(board.index_to_cell()[tile])
+}
+
+/// Every legal placement of the piece on the board that covers `cell`
+pub fn placements_covering(board: &CellShape, piece: Pentomino, cell: Coord) -> Vec<Placement> {
+    orientations(piece)
+        .into_iter()
+        .flat_map(|(transform, shape)| {
+            shape
+                .filled_tiles()
+                .map(move |tile| Placement { piece, transform, offset: cell - tile })
+                .collect::<Vec<_>>()
+        })
+        .filter(|placement| placement.shape().is_subset_of(board, placement.offset))
+        .collect()
+}
+
+/// Covers the board greedily, without backtracking, so the result may leave gaps even on a board that can be tiled.
+/// Each step takes the first uncovered tile and lays the first unused piece and orientation that fits over it.
+/// Every pentomino covers five tiles, so no placement covers more than another and the first one wins.
+/// When no piece fits, the tile is left as a gap and the next uncovered tile is tried.
+pub fn greedy_cover(board: &CellShape) -> Vec<Placement> {
+    let mut uncovered: HashSet<Coord> = board.filled_tiles().collect();
+    let mut placements: Vec<Placement> = Vec::new();
+    for cell in board.filled_tiles() {
+        if !uncovered.contains(&cell) {
+            continue;
+        }
+        let placement = unused_pieces(&placements)
+            .into_iter()
+            .flat_map(|piece| placements_covering(board, piece, cell))
+            .find(|placement| placement.cells().iter().all(|c| uncovered.contains(c)));
+        if let Some(placement) = placement {
+            for c in placement.cells() {
+                uncovered.remove(&c);
+            }
+            placements.push(placement);
+        }
+    }
+    placements
+}
+
+/// Whether some connected region of the board has a number of tiles that isn't a multiple of 5,
+/// which no set of pentominoes can fill. The search checks this after every placement.
+pub fn has_unfillable_region(board: &CellShape) -> bool {
+    region_sizes(board).into_iter().any(|size| !size.is_multiple_of(5))
+}
+
+/// How many connected regions of the board the placement would leave with fewer than 5 tiles.
+/// Those are holes no pentomino fits in, so a placement leaving any can't be part of a solution.
+pub fn holes_after_placement(board: &CellShape, placement: &Placement) -> usize {
+    let covered = placement.cells();
+    let open = board.filled_tiles().filter(|c| !covered.contains(c)).collect();
+    region_sizes(&CellShape::from_coordinate_list(open))
+        .into_iter()
+        .filter(|&size| size < 5)
+        .count()
+}
+
+// The number of tiles in each orthogonally connected region of the board
+fn region_sizes(board: &CellShape) -> Vec<usize> {
+    let mut seen = HashSet::new();
+    let mut sizes = Vec::new();
+    for start in board.filled_tiles() {
+        if !seen.insert(start) {
+            continue;
+        }
+        let mut size: usize = 0;
+        let mut stack = vec![start];
+        while let Some(c) = stack.pop() {
+            size += 1;
+            for n in c.neighbors4() {
+                if board[n].is_filled() && seen.insert(n) {
+                    stack.push(n);
+                }
+            }
+        }
+        sizes.push(size);
+    }
+    sizes
+}
+
+/// A quick necessary condition for the board to be tiled by the pentominoes, each used at most once.
+/// The board needs a multiple of 5 tiles, no more than the pieces can cover, and must pass a checkerboard
+/// coloring argument: the X pentomino covers 4 tiles of one color and 1 of the other, while every other
+/// piece covers 3 and 2, which limits how unbalanced the board's colors can be.
+/// Returning true does not guarantee that a solution exists.
+pub fn is_potentially_tileable(board: &CellShape) -> bool {
+    let size = board.size();
+    if !size.is_multiple_of(5) || size > 5 * PENTOMINOES.len() {
+        return false;
+    }
+    let pieces = (size / 5) as isize;
+    let (even, odd) = board.count_parity();
+    let (even, odd) = (even as isize, odd as isize);
+    let max_imbalance = if pieces == 0 { 0 } else { pieces + 2 };
+    (even - odd).abs() <= max_imbalance
+}
+
+/// Tiles a scaled up copy of `target` with the other eleven pentominoes.
+/// The replica has `scale * scale` times the area of a pentomino, so that many pieces are needed.
+pub fn solve_replica(target: Pentomino, scale: usize) -> Option<Vec<Placement>> {
+    let others: Vec<Pentomino> = PENTOMINOES
+        .iter()
+        .copied()
+        .filter(|&p| p != target)
+        .collect();
+    if scale == 0 || scale * scale > others.len() {
+        return None;
+    }
+    let region = target.representative().scale(scale);
+    solve_with_pieces(&region, &others)
+}
+
+// Every distinct orientation of the piece, in the order of RIGID_SYMMETRIES
+fn orientations(piece: Pentomino) -> Vec<(Transform, CellShape)> {
+    orientations_of(&piece.representative(), &RIGID_SYMMETRIES)
+}
+
+// Every distinct orientation of the shape under the symmetries, in their order
+fn orientations_of(shape: &CellShape, symmetries: &[Transform]) -> Vec<(Transform, CellShape)> {
+    let mut seen = HashSet::new();
+    symmetries
+        .iter()
+        .map(|&t| (t, t.transform_shape(shape.clone())))
+        .filter(|(_, shape)| seen.insert(shape.clone()))
+        .collect()
+}
+
+// The board indices covered by the shape moved by `offset`, or None if it doesn't fit on the board
+fn covered_indices(shape: &CellShape, offset: Vec2D, index: &HashMap<Coord, usize>) -> Option<Vec<usize>> {
+    shape
+        .filled_tiles()
+        .map(|c| index.get(&(c + offset)).copied())
+        .collect()
+}
+
+// A piece the search may place, as its distinct orientations and how many times it may be used
+struct SearchPiece {
+    orientations: Vec<(Transform, CellShape)>,
+    limit: usize,
+}
+
+impl SearchPiece {
+    fn pentomino(piece: Pentomino, config: &SolverConfig) -> SearchPiece {
+        let symmetries: &[Transform] = if config.allow_reflections {
+            &RIGID_SYMMETRIES
+        } else {
+            &ROTATIONS
+        };
+        SearchPiece {
+            orientations: orientations_of(&piece.representative(), symmetries),
+            limit: 1,
+        }
+    }
+}
+
+// A legal placement of one piece, along with the board tiles it covers
+struct Candidate<P> {
+    piece: usize,
+    mask: u128,
+    placement: P,
+}
+
+// A set of candidate indices, one bit per candidate
+type CandidateSet = Vec<u64>;
+
+// Puts the items in an order picked by the seed, with a splitmix64 generator driving a Fisher-Yates shuffle
+fn shuffle<T>(items: &mut [T], seed: u64) {
+    let mut state = seed;
+    let mut next = || {
+        state = state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    };
+    for i in (1..items.len()).rev() {
+        let j = (next() % (i as u64 + 1)) as usize;
+        items.swap(i, j);
+    }
+}
+
+// Takes the members of `other` out of `set`
+fn remove_all(set: &mut [u64], other: &[u64]) {
+    set.iter_mut().zip(other).for_each(|(word, other)| *word &= !other);
+}
+
+// State of the backtracking search.
+// Each step fills the open tile with the fewest fitting candidates, so dead ends are found early.
+struct Search<'a, P = Placement> {
+    candidates: Vec<Candidate<P>>,
+    // covering[i] holds the candidates that cover tile i
+    covering: Vec<CandidateSet>,
+    // by_piece[p] holds the candidates placing piece p
+    by_piece: Vec<CandidateSet>,
+    // the candidates that neither overlap the covered tiles nor use up a piece
+    fitting: CandidateSet,
+    // saved[depth] holds `fitting` from when the search was that many placements deep
+    saved: Vec<CandidateSet>,
+    // neighbors[i] holds the tiles orthogonally next to tile i
+    neighbors: Vec<u128>,
+    // the size of every piece, when they are all the same
+    region_size: Option<u32>,
+    full: u128,
+    covered: u128,
+    // how many more times each piece may be used
+    remaining: Vec<usize>,
+    placements: Vec<P>,
+    // the covered tiles and pieces left of every state already searched without finding a solution,
+    // when memoizing
+    dead_ends: Option<HashSet<(u128, Vec<usize>)>>,
+    stats: SearchStats,
+    trace: Option<Box<dyn FnMut(SearchEvent<P>) + 'a>>,
+    deadline: Option<Instant>,
+}
+
+impl<'a> Search<'a> {
+    fn new(board: &CellShape, pieces: &[Pentomino], config: &SolverConfig) -> Option<Search<'a>> {
+        // every tile gets covered exactly once, so the pieces are all used when their sizes add up to the board
+        if config.require_all_pieces && board.size() != pieces.iter().map(|p| p.representative().size()).sum() {
+            return None;
+        }
+        let search_pieces: Vec<SearchPiece> = pieces
+            .iter()
+            .map(|&p| SearchPiece::pentomino(p, config))
+            .collect();
+        let index = board.cell_index();
+        let locate = |c| index.get(&c).copied();
+        let mut search = Search::with_locator(board, &search_pieces, locate, config.seed, |p, transform, offset| Placement {
+            piece: pieces[p],
+            transform,
+            offset,
+        })?;
+        search.dead_ends = config.memoize.then(HashSet::new);
+        search.deadline = config.deadline;
+        Some(search)
+    }
+}
+
+impl<'a, P: Copy> Search<'a, P> {
+    // Builds every candidate, labeling them with `make(piece index, transform, offset)`.
+    // Returns None when there are too many tiles for the pieces to possibly cover
+    fn with_pieces(
+        board: &CellShape,
+        pieces: &[SearchPiece],
+        make: impl Fn(usize, Transform, Vec2D) -> P,
+    ) -> Option<Search<'a, P>> {
+        let index = board.cell_index();
+        Search::with_locator(board, pieces, |c| index.get(&c).copied(), None, make)
+    }
+
+    // Like `with_pieces`, using `locate` to find the board index of a coordinate, if it is on the board.
+    // With a seed, the candidates are tried in a shuffled order
+    fn with_locator(
+        board: &CellShape,
+        pieces: &[SearchPiece],
+        locate: impl Fn(Coord) -> Option<usize>,
+        seed: Option<u64>,
+        make: impl Fn(usize, Transform, Vec2D) -> P,
+    ) -> Option<Search<'a, P>> {
+        let tiles = board.index_to_cell();
+        let capacity: usize = pieces
+            .iter()
+            .map(|p| p.orientations[0].1.size() * p.limit)
+            .sum();
+        if tiles.len() > capacity || tiles.len() > u128::BITS as usize {
+            return None;
+        }
+
+        let mut seen = HashSet::new();
+        let mut candidates = Vec::new();
+        for (p, piece) in pieces.iter().enumerate() {
+            for (transform, shape) in &piece.orientations {
+                let Some(anchor) = shape.filled_tiles().next() else {
+                    continue;
+                };
+                for &target in &tiles {
+                    let offset = target - anchor;
+                    let covered: Option<Vec<usize>> = shape
+                        .filled_tiles()
+                        .map(|c| locate(c + offset))
+                        .collect();
+                    let Some(covered) = covered else {
+                        continue;
+                    };
+                    let mask = covered.iter().fold(0u128, |mask, &i| mask | 1 << i);
+                    // a board that wraps around may have the piece overlap itself,
+                    // or reach the same tiles from another offset
+                    if mask.count_ones() as usize != covered.len() || !seen.insert((p, mask)) {
+                        continue;
+                    }
+                    candidates.push(Candidate {
+                        piece: p,
+                        mask,
+                        placement: make(p, *transform, offset),
+                    });
+                }
+            }
+        }
+
+        if let Some(seed) = seed {
+            shuffle(&mut candidates, seed);
+        }
+
+        let words = candidates.len().div_ceil(64);
+        let mut covering = vec![vec![0; words]; tiles.len()];
+        let mut by_piece = vec![vec![0; words]; pieces.len()];
+        for (c, candidate) in candidates.iter().enumerate() {
+            let bit = 1 << (c % 64);
+            let mut tiles = candidate.mask;
+            while tiles != 0 {
+                covering[tiles.trailing_zeros() as usize][c / 64] |= bit;
+                tiles &= tiles - 1;
+            }
+            by_piece[candidate.piece][c / 64] |= bit;
+        }
+        let mut fitting = vec![u64::MAX; words];
+        for (p, piece) in pieces.iter().enumerate() {
+            if piece.limit == 0 {
+                remove_all(&mut fitting, &by_piece[p]);
+            }
+        }
+
+        let neighbors = tiles
+            .iter()
+            .map(|c| c
+                .neighbors4()
+                .iter()
+                .filter_map(|&n| locate(n))
+                .fold(0u128, |mask, i| mask | 1 << i))
+            .collect();
+        // open regions can only be filled when their size is a multiple of the piece size,
+        // which is only known when every piece has the same size
+        let sizes: HashSet<usize> = pieces.iter().map(|p| p.orientations[0].1.size()).collect();
+        let region_size = match sizes.into_iter().collect::<Vec<_>>()[..] {
+            [size] => Some(size as u32),
+            _ => None,
+        };
+
+        Some(Search {
+            candidates,
+            covering,
+            by_piece,
+            fitting,
+            neighbors,
+            region_size,
+            saved: Vec::new(),
+            full: u128::MAX.checked_shr(u128::BITS - tiles.len() as u32).unwrap_or(0),
+            covered: 0,
+            remaining: pieces.iter().map(|p| p.limit).collect(),
+            placements: Vec::new(),
+            dead_ends: None,
+            stats: SearchStats::default(),
+            trace: None,
+            deadline: None,
+        })
+    }
+
+    // Whether some connected region of open tiles has a size the pieces can't add up to
+    fn has_unfillable_region(&self) -> bool {
+        let Some(size) = self.region_size else {
+            return false;
+        };
+        let mut open = self.full & !self.covered;
+        while open != 0 {
+            // flood fill from the lowest open tile
+            let mut region = open & open.wrapping_neg();
+            let mut frontier = region;
+            while frontier != 0 {
+                let mut grown = 0;
+                while frontier != 0 {
+                    grown |= self.neighbors[frontier.trailing_zeros() as usize];
+                    frontier &= frontier - 1;
+                }
+                frontier = grown & open & !region;
+                region |= frontier;
+            }
+            if !region.count_ones().is_multiple_of(size) {
+                return true;
+            }
+            open &= !region;
+        }
+        false
+    }
+
+    // The open tile with the fewest fitting candidates, preferring the lowest tile on ties
+    fn most_constrained(&self) -> Option<(usize, u32)> {
+        let mut open = self.full & !self.covered;
+        let mut best: Option<(usize, u32)> = None;
+        while open != 0 {
+            let tile = open.trailing_zeros() as usize;
+            open &= open - 1;
+            let limit = best.map_or(u32::MAX, |(_, best_count)| best_count);
+            let mut count = 0;
+            for (covering, fitting) in self.covering[tile].iter().zip(&self.fitting) {
+                count += (covering & fitting).count_ones();
+                // counting past the best so far can't change the answer
+                if count >= limit {
+                    break;
+                }
+            }
+            if count < limit {
+                best = Some((tile, count));
+                if count <= 1 {
+                    break;
+                }
+            }
+        }
+        best
+    }
+
+    // Covers the most constrained open tile with every candidate that fits, recursing on the rest of the board
+    fn run<F>(&mut self, on_solution: &mut F) -> ControlFlow<()>
+    where
+        F: FnMut(&[P]) -> ControlFlow<()>,
+    {
+        if self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            self.stats.timed_out = true;
+            return ControlFlow::Break(());
+        }
+        self.stats.nodes += 1;
+        if self.has_unfillable_region() {
+            self.stats.pruned += 1;
+            return ControlFlow::Continue(());
+        }
+        let Some((tile, count)) = self.most_constrained() else {
+            self.stats.solutions += 1;
+            return on_solution(&self.placements);
+        };
+        if count == 0 {
+            return ControlFlow::Continue(());
+        }
+        let state = self.dead_ends.as_ref().map(|_| (self.covered, self.remaining.clone()));
+        if let (Some(dead_ends), Some(state)) = (&self.dead_ends, &state) {
+            if dead_ends.contains(state) {
+                self.stats.pruned += 1;
+                return ControlFlow::Continue(());
+            }
+        }
+        let solutions_before = self.stats.solutions;
+
+        // remember which candidates fit here, to restore after each choice
+        let depth = self.placements.len();
+        if self.saved.len() == depth {
+            self.saved.push(self.fitting.clone());
+        } else {
+            self.saved[depth].copy_from_slice(&self.fitting);
+        }
+
+        let mut flow = ControlFlow::Continue(());
+        for w in 0..self.fitting.len() {
+            let mut word = self.covering[tile][w] & self.saved[depth][w];
+            while word != 0 {
+                let c = w * 64 + word.trailing_zeros() as usize;
+                word &= word - 1;
+
+                let Candidate { piece, mask, placement } = self.candidates[c];
+                self.covered |= mask;
+                self.remaining[piece] -= 1;
+                let mut tiles = mask;
+                while tiles != 0 {
+                    remove_all(&mut self.fitting, &self.covering[tiles.trailing_zeros() as usize]);
+                    tiles &= tiles - 1;
+                }
+                if self.remaining[piece] == 0 {
+                    remove_all(&mut self.fitting, &self.by_piece[piece]);
+                }
+                self.placements.push(placement);
+                self.emit(SearchEvent::Place(placement));
+
+                flow = self.run(on_solution);
+                if flow.is_continue() {
+                    self.emit(SearchEvent::Remove(placement));
+                }
+
+                self.placements.pop();
+                self.fitting.copy_from_slice(&self.saved[depth]);
+                self.remaining[piece] += 1;
+                self.covered &= !mask;
+
+                if flow.is_break() {
+                    return flow;
+                }
+            }
+        }
+        if let (Some(dead_ends), Some(state)) = (&mut self.dead_ends, state) {
+            if self.stats.solutions == solutions_before {
+                dead_ends.insert(state);
+            }
+        }
+        flow
+    }
+
+    fn emit(&mut self, event: SearchEvent<P>) {
+        if let Some(trace) = &mut self.trace {
+            trace(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(piece: Pentomino, x: isize, y: isize) -> Placement {
+        Placement {
+            piece,
+            transform: Transform::identity(),
+            offset: Vec2D::new(x, y),
+        }
+    }
+
+    // Builds the board covered exactly by the given placements
+    fn board_of(placements: &[Placement]) -> CellShape {
+        let coords = placements
+            .iter()
+            .flat_map(|p| p.cells())
+            .collect();
+        CellShape::from_coordinate_list(coords)
+    }
+
+    #[test]
+    fn valid_solution() {
+        let placements = [
+            at(Pentomino::I, 0, 0),
+            at(Pentomino::L, 1, 0),
+            at(Pentomino::P, 1, 4),
+        ];
+        let board = board_of(&placements);
+
+        assert_eq!(validate_solution(&board, &placements), Ok(()));
+    }
+
+    #[test]
+    fn overlapping_solution() {
+        let placements = [
+            at(Pentomino::I, 0, 0),
+            at(Pentomino::L, 0, 0),
+        ];
+        let board = board_of(&placements);
+
+        assert!(validate_solution(&board, &placements).is_err());
+    }
+
+    #[test]
+    fn uncovered_cell() {
+        let board = board_of(&[at(Pentomino::I, 0, 0), at(Pentomino::L, 1, 0)]);
+        let placements = [at(Pentomino::I, 0, 0)];
+
+        assert!(validate_solution(&board, &placements).is_err());
+    }
+
+    #[test]
+    fn outside_board() {
+        let board = board_of(&[at(Pentomino::I, 0, 0)]);
+        let placements = [at(Pentomino::I, 1, 0)];
+
+        assert!(validate_solution(&board, &placements).is_err());
+    }
+
+    #[test]
+    fn repeated_piece() {
+        let placements = [
+            at(Pentomino::I, 0, 0),
+            at(Pentomino::I, 1, 0),
+        ];
+        let board = board_of(&placements);
+
+        assert!(validate_solution(&board, &placements).is_err());
+    }
+
+    #[test]
+    fn empty_board() {
+        assert_eq!(solve_region(&CellShape::empty()), Some(Vec::new()));
+    }
+
+    #[test]
+    fn replica_doubled_p() {
+        let solution = solve_replica(Pentomino::P, 2).unwrap();
+        let region = Pentomino::P.representative().scale(2);
+
+        assert_eq!(solution.len(), 4);
+        assert!(solution.iter().all(|p| p.piece != Pentomino::P));
+        assert_eq!(validate_solution(&region, &solution), Ok(()));
+    }
+
+    #[test]
+    fn replica_doubled_x_unsolvable() {
+        assert_eq!(solve_replica(Pentomino::X, 2), None);
+    }
+
+    #[test]
+    fn replica_bad_scale() {
+        // the other pieces are all different from the target
+        assert_eq!(solve_replica(Pentomino::L, 1), None);
+        // needs more pieces than there are
+        assert_eq!(solve_replica(Pentomino::L, 4), None);
+    }
+
+    #[test]
+    fn traced_replay_never_overlaps() {
+        let board = CellShape::rectangle(5, 4);
+        let mut covered = HashSet::new();
+        let mut events = 0;
+
+        let solution = solve_region_traced(&board, |event| {
+            events += 1;
+            match event {
+                SearchEvent::Place(p) => {
+                    for c in p.cells() {
+                        assert!(board[c].is_filled());
+                        assert!(covered.insert(c), "{:?} overlaps at {:?}", p.piece, c);
+                    }
+                }
+                SearchEvent::Remove(p) => {
+                    for c in p.cells() {
+                        assert!(covered.remove(&c));
+                    }
+                }
+            }
+        });
+
+        let solution = solution.unwrap();
+        assert!(events > solution.len());
+        assert_eq!(covered.len(), 20);
+        assert_eq!(validate_solution(&board, &solution), Ok(()));
+    }
+
+    #[test]
+    fn symmetries_of_boards() {
+        assert_eq!(board_symmetries(&CellShape::rectangle(8, 8)).len(), 8);
+        let rectangle = board_symmetries(&CellShape::rectangle(10, 6));
+        assert_eq!(rectangle, vec![
+            Transform::identity(),
+            Transform::mirror_horizontal(),
+            Transform::mirror_vertical(),
+            Transform::rotate180(),
+        ]);
+        assert_eq!(board_symmetries(&Pentomino::F.representative()), vec![Transform::identity()]);
+        assert_eq!(board_symmetries(&Pentomino::T.representative()).len(), 2);
+    }
+
+    #[test]
+    fn distinct_solutions_3x20() {
+        let board = CellShape::rectangle(20, 3);
+        assert_eq!(count_distinct_solutions(&board, &board_symmetries(&board)), Ok(2));
+        // without any symmetries every solution is distinct
+        assert_eq!(count_distinct_solutions(&CellShape::rectangle(20, 3), &[]), Ok(8));
+    }
+
+    #[test]
+    fn distinct_solutions_6x10() {
+        let board = CellShape::rectangle(10, 6);
+        assert_eq!(count_distinct_solutions(&board, &board_symmetries(&board)), Ok(2339));
+    }
+
+    #[test]
+    fn distinct_solutions_not_a_symmetry() {
+        let symmetries = [Transform::rotate90()];
+        assert!(count_distinct_solutions(&CellShape::rectangle(10, 6), &symmetries).is_err());
+    }
+
+    #[test]
+    fn render() {
+        let placements = [
+            at(Pentomino::I, 0, 0),
+            at(Pentomino::L, 1, 0),
+        ];
+        let board = CellShape::rectangle(3, 5);
+
+        let expected = concat!(
+            "IL.\n",
+            "IL.\n",
+            "IL.\n",
+            "ILL\n",
+            "I..\n",
+        );
+
+        assert_eq!(render_solution(&board, &placements), expected);
+    }
+
+    #[test]
+    fn potentially_tileable() {
+        assert!(is_potentially_tileable(&CellShape::rectangle(10, 6)));
+        assert!(is_potentially_tileable(&CellShape::rectangle(5, 4)));
+    }
+
+    #[test]
+    fn not_potentially_tileable() {
+        assert!(!is_potentially_tileable(&CellShape::rectangle(7, 1)));
+        // too big for the twelve pieces
+        assert!(!is_potentially_tileable(&CellShape::rectangle(13, 5)));
+        // every tile is the same color
+        let checkered = CellShape::from_coordinate_list(
+            (0..10).map(|i| Coord::new(2 * i, 0)).collect()
+        );
+        assert!(!is_potentially_tileable(&checkered));
+    }
+
+    #[test]
+    fn min_cost() {
+        let board = CellShape::rectangle(20, 3);
+        // prefer the X as close to the top left as possible
+        let cost = |p: &Placement| match p.piece {
+            Pentomino::X => p.offset.x + p.offset.y,
+            _ => 0,
+        } as i64;
+
+        let mut all = Vec::new();
+        for_each_solution(&board, &PENTOMINOES, |placements| {
+            all.push(placements.to_vec());
+            ControlFlow::Continue(())
+        });
+        let cheapest = all
+            .iter()
+            .map(|solution| solution.iter().map(cost).sum::<i64>())
+            .min()
+            .unwrap();
+
+        let (total, solution) = solve_min_cost(&board, cost).unwrap();
+
+        assert_eq!(total, cheapest);
+        assert_eq!(solution.iter().map(cost).sum::<i64>(), total);
+        assert_eq!(validate_solution(&board, &solution), Ok(()));
+    }
+
+    #[test]
+    fn min_cost_unsolvable() {
+        assert_eq!(solve_min_cost(&CellShape::rectangle(2, 5), |_| 0), None);
+    }
+
+    #[test]
+    fn parse_round_trip() {
+        let board = CellShape::rectangle(10, 6);
+        let mut solution = solve_region(&board).unwrap();
+
+        let mut parsed = parse_solution(&render_solution(&board, &solution)).unwrap();
+
+        solution.sort_by_key(|p| p.piece);
+        parsed.sort_by_key(|p| p.piece);
+        assert_eq!(parsed, solution);
+    }
+
+    #[test]
+    fn parse_not_a_pentomino() {
+        assert!(parse_solution("IIII").is_err());
+        assert!(parse_solution("QQQQQ").is_err());
+        assert!(parse_solution("LLLL\n..L.").is_err());
+    }
+
+    #[test]
+    fn mixed_pentomino_and_tetromino() {
+        use crate::cell_shape::Tile::{Empty as o, Filled as F};
+
+        let l_tetromino = CellShape::from_2darray([
+            [F, o],
+            [F, o],
+            [F, F],
+        ]);
+        let pieces = [
+            (Pentomino::P.representative(), 1),
+            (l_tetromino, 1),
+        ];
+        let board = CellShape::rectangle(3, 3);
+
+        let solution = solve_mixed(&board, &pieces).unwrap();
+
+        assert_eq!(solution.len(), 2);
+        let mut covered: Vec<Coord> = solution
+            .iter()
+            .flat_map(|p| p.cells(&pieces[p.piece].0))
+            .collect();
+        covered.sort_by_key(|c| (c.x, c.y));
+        assert_eq!(covered, board.filled_tiles().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn mixed_respects_limits() {
+        use crate::cell_shape::Tile::{Empty as o, Filled as F};
+
+        let l_tetromino = CellShape::from_2darray([
+            [F, o],
+            [F, o],
+            [F, F],
+        ]);
+        let board = CellShape::rectangle(2, 4);
+
+        assert_eq!(solve_mixed(&board, &[(l_tetromino.clone(), 1)]), None);
+        assert_eq!(solve_mixed(&board, &[(l_tetromino, 2)]).map(|s| s.len()), Some(2));
+    }
+
+    #[test]
+    fn sorted_solutions_3x20() {
+        let solutions = solutions_sorted(20, 3);
+        let board = CellShape::rectangle(20, 3);
+
+        assert_eq!(solutions.len(), 8);
+        let expected = concat!(
+            "UUXIIIIINNNFTWYYYYZV\n",
+            "UXXXPPLNNFFFTWWYZZZV\n",
+            "UUXPPPLLLLFTTTWWZVVV\n",
+        );
+        assert_eq!(render_solution(&board, &solutions[0]), expected);
+        assert_eq!(solutions, solutions_sorted(20, 3));
+    }
+
+    #[test]
+    fn reflections_disallowed() {
+        // the L pentomino flipped over
+        let board = Pentomino::L.representative().flip_lr();
+        let no_reflections = SolverConfig {
+            allow_reflections: false,
+            ..SolverConfig::default()
+        };
+
+        assert_eq!(solve_with_config(&board, &PENTOMINOES, &no_reflections), None);
+
+        let solution = solve_with_config(&board, &PENTOMINOES, &SolverConfig::default()).unwrap();
+        assert_eq!(solution.len(), 1);
+        assert_eq!(solution[0].piece, Pentomino::L);
+    }
+
+    #[test]
+    fn placement_rows_cover_five_cells() {
+        let board = CellShape::rectangle(5, 4);
+        let index = board.cell_index();
+
+        let rows = placement_rows(&board, &PENTOMINOES);
+
+        assert!(!rows.is_empty());
+        for (placement, covered) in &rows {
+            assert_eq!(covered.len(), 5);
+            let expected: Vec<usize> = placement.cells().iter().map(|c| index[c]).collect();
+            assert_eq!(*covered, expected);
+        }
+        // the I pentomino only fits along the long side
+        let i_rows = rows.iter().filter(|(p, _)| p.piece == Pentomino::I).count();
+        assert_eq!(i_rows, 4);
+    }
+
+    #[test]
+    fn torus_ring() {
+        // a ring of 5 tiles can only be covered by the I pentomino, and every offset covers the same tiles
+        assert_eq!(count_solutions_torus(5, 1), 1);
+        assert_eq!(count_solutions_torus(1, 5), 1);
+        assert_eq!(count_solutions_torus(7, 1), 0);
+    }
+
+    #[test]
+    fn torus_has_more_solutions() {
+        let mut flat = 0;
+        for_each_solution(&CellShape::rectangle(5, 3), &PENTOMINOES, |_| {
+            flat += 1;
+            ControlFlow::Continue(())
+        });
+        let torus = count_solutions_torus(5, 3);
+        assert!(torus > flat, "{torus} <= {flat}");
+    }
+
+    // Checks the JSON value starting at `s`, returning what follows it.
+    // Only handles what solution_to_json writes: arrays, objects, strings without escapes and integers
+    fn skip_json_value(s: &str) -> Option<&str> {
+        let s = s.trim_start();
+        let (open, close) = match s.chars().next()? {
+            '"' => return s[1..].find('"').map(|end| &s[end + 2..]),
+            '[' => ('[', ']'),
+            '{' => ('{', '}'),
+            _ => {
+                let end = s.find(|c: char| !c.is_ascii_digit() && c != '-').unwrap_or(s.len());
+                return (end > 0).then(|| &s[end..]);
+            }
+        };
+        let mut rest = s.strip_prefix(open)?.trim_start();
+        if let Some(rest) = rest.strip_prefix(close) {
+            return Some(rest);
+        }
+        loop {
+            if open == '{' {
+                rest = skip_json_value(rest)?.trim_start().strip_prefix(':')?;
+            }
+            rest = skip_json_value(rest)?.trim_start();
+            match rest.strip_prefix(',') {
+                Some(next) => rest = next,
+                None => return rest.strip_prefix(close),
+            }
+        }
+    }
+
+    #[test]
+    fn solution_json() {
+        let placements = solve_region(&CellShape::rectangle(6, 10)).unwrap();
+        let json = solution_to_json(&placements);
+        assert_eq!(skip_json_value(&json), Some(""), "{json}");
+        assert_eq!(json.matches(r#""piece":"#).count(), 12);
+        assert_eq!(solution_to_json(&[]), "[]");
+        assert_eq!(
+            solution_to_json(&[Placement { piece: Pentomino::F, transform: Transform::rotate90(), offset: Vec2D::new(2, 3) }]),
+            r#"[{"piece":"F","transform":"rotate90","offset":[2,3]}]"#
+        );
+    }
+
+    #[test]
+    fn solvable_rectangle_dimensions() {
+        assert_eq!(solvable_rectangles(), vec![(3, 20), (4, 15), (5, 12), (6, 10)]);
+    }
+
+    #[test]
+    fn most_constrained_cell_next_to_hole() {
+        // the right column's middle tile is cut off on the left, so only pieces running down that column cover it
+        use crate::cell_shape::Tile::{Empty as o, Filled as F};
+        let board = CellShape::from_2darray([
+            [F, F, F, F, F],
+            [F, F, F, o, F],
+            [F, F, F, F, F],
+        ]);
+        assert_eq!(most_constrained_cell(&board, &PENTOMINOES), Some(Coord::new(4, 1)));
+        assert_eq!(most_constrained_cell(&CellShape::empty(), &PENTOMINOES), None);
+    }
+
+    #[test]
+    fn no_duplicate_solutions() {
+        // symmetric pieces like X must not be tried in orientations that cover the same tiles
+        assert_eq!(orientations(Pentomino::X).len(), 1);
+        assert_eq!(orientations(Pentomino::I).len(), 2);
+
+        let board = CellShape::rectangle(6, 10);
+        let mut rendered = HashSet::new();
+        for_each_solution(&board, &PENTOMINOES, |placements| {
+            rendered.insert(render_solution(&board, placements));
+            ControlFlow::Continue(())
+        });
+        assert_eq!(rendered.len() as u64, count_solutions(6, 10));
+        assert_eq!(rendered.len(), 9356);
+    }
+
+    #[test]
+    fn capped_count() {
+        // stopping at the cap is what keeps this fast, the full count is 9356
+        assert_eq!(count_solutions_capped(&CellShape::rectangle(6, 10), 5), 5);
+        assert_eq!(count_solutions_capped(&CellShape::rectangle(20, 3), 100), 8);
+        assert_eq!(count_solutions_capped(&CellShape::rectangle(20, 3), 0), 0);
+    }
+
+    #[test]
+    fn memoizing_visits_fewer_nodes() {
+        // the classic 8x8 board with a 2x2 hole, where different placement orders often leave the same tiles open
+        let board = CellShape::from_coordinate_list(
+            CellShape::rectangle(8, 8)
+                .filled_tiles()
+                .filter(|c| !(3..=4).contains(&c.x) || !(3..=4).contains(&c.y))
+                .collect(),
+        );
+        let plain = SolverConfig::default();
+        let memoized = SolverConfig {
+            memoize: true,
+            ..SolverConfig::default()
+        };
+        let (solution, plain_stats) = solve_with_stats(&board, &PENTOMINOES, &plain);
+        let (memo_solution, memo_stats) = solve_with_stats(&board, &PENTOMINOES, &memoized);
+        assert!(validate_solution(&board, &solution.unwrap()).is_ok());
+        assert!(validate_solution(&board, &memo_solution.unwrap()).is_ok());
+        assert!(memo_stats.pruned > 0);
+        assert!(memo_stats.nodes < plain_stats.nodes, "{memo_stats:?} vs {plain_stats:?}");
+
+        let no_reflections = SolverConfig {
+            allow_reflections: false,
+            memoize: true,
+            ..SolverConfig::default()
+        };
+        assert_eq!(solve_with_stats(&CellShape::rectangle(20, 3), &PENTOMINOES, &no_reflections).0, None);
+    }
+
+    #[test]
+    fn placements_covering_cell() {
+        let board = CellShape::rectangle(6, 10);
+        // away from the edges, the cell can be any of the X's five tiles
+        let central = placements_covering(&board, Pentomino::X, Coord::new(2, 4));
+        assert_eq!(central.len(), 5);
+        assert!(central.iter().all(|p| p.cells().contains(&Coord::new(2, 4))));
+        // on an edge only an arm reaches it, and no X fits in a corner
+        assert_eq!(placements_covering(&board, Pentomino::X, Coord::new(0, 4)).len(), 1);
+        assert_eq!(placements_covering(&board, Pentomino::X, Coord::new(0, 0)).len(), 0);
+    }
+
+    #[test]
+    fn unfillable_region() {
+        assert!(!has_unfillable_region(&CellShape::rectangle(6, 10)));
+        // a 3 tile pocket cut off from the rest of the board, which alone has 20 tiles
+        let mut tiles = CellShape::rectangle(5, 4).index_to_cell();
+        tiles.extend([Coord::new(6, 0), Coord::new(6, 1), Coord::new(6, 2)]);
+        let board = CellShape::from_coordinate_list(tiles);
+        assert!(has_unfillable_region(&board));
+
+        let (solution, stats) = solve_with_stats(&board, &PENTOMINOES, &SolverConfig::default());
+        assert_eq!(solution, None);
+        assert_eq!(stats.nodes, 1);
+    }
+
+    #[test]
+    fn boxed_rendering() {
+        let placements = [
+            Placement { piece: Pentomino::I, transform: Transform::rotate90(), offset: Vec2D::new(0, 0) },
+            Placement { piece: Pentomino::L, transform: Transform::identity(), offset: Vec2D::new(5, 0) },
+        ];
+        let board = board_of(&placements);
+        let boxed = render_solution_boxed(&board, &placements);
+        assert_eq!(boxed, concat!(
+            "┏━━┯━━┯━━┯━━┯━━┳━━┓   \n",
+            "┃  │  │  │  │  ┃  ┃   \n",
+            "┗━━┷━━┷━━┷━━┷━━╉──┨   \n",
+            "               ┃  ┃   \n",
+            "               ┠──┨   \n",
+            "               ┃  ┃   \n",
+            "               ┠──╄━━┓\n",
+            "               ┃  │  ┃\n",
+            "               ┗━━┷━━┛\n",
+        ));
+        // heavy lines separate different pieces, light ones tiles of the same piece
+        assert!(boxed.contains('╉') && boxed.contains('│') && boxed.contains('─'));
+    }
+
+    #[test]
+    fn pairs_for_2x5() {
+        use Pentomino::*;
+        assert_eq!(pairs_tiling_2x5(), vec![(I, I), (L, L), (P, P)]);
+    }
+
+    #[test]
+    fn seeded_search_order() {
+        let board = CellShape::rectangle(6, 10);
+        let first = |seed| {
+            let config = SolverConfig {
+                seed: Some(seed),
+                ..SolverConfig::default()
+            };
+            solve_with_config(&board, &PENTOMINOES, &config).unwrap()
+        };
+        let (one, two) = (first(1), first(2));
+        assert!(validate_solution(&board, &one).is_ok());
+        assert!(validate_solution(&board, &two).is_ok());
+        assert_ne!(render_solution(&board, &one), render_solution(&board, &two));
+        assert_eq!(one, first(1));
+        assert_eq!(two, first(2));
+    }
+
+    #[test]
+    fn corner_distribution() {
+        let counts = corner_piece_distribution(6, 10);
+        assert_eq!(counts.values().sum::<u64>(), 9356);
+        // the X needs a neighbor on all four sides, so it can't sit in a corner
+        assert!(!counts.contains_key(&Pentomino::X));
+
+        let counts = corner_piece_distribution(20, 3);
+        assert_eq!(counts.values().sum::<u64>(), count_solutions(20, 3));
+    }
+
+    #[test]
+    fn equivalent_solutions() {
+        let board = CellShape::rectangle(10, 6);
+        let solution = solve_region(&board).unwrap();
+        // turning the rendered board upside down reverses every line and the line order
+        let rendered = render_solution(&board, &solution);
+        let turned: Vec<String> = rendered.lines().rev().map(|line| line.chars().rev().collect()).collect();
+        let turned = parse_solution(&turned.join("\n")).unwrap();
+        assert_ne!(render_solution(&board, &turned), rendered);
+        assert!(solutions_equivalent(&board, &solution, &turned));
+        assert!(solutions_equivalent(&board, &turned, &solution));
+
+        let mut solutions = Vec::new();
+        for_each_solution(&CellShape::rectangle(20, 3), &PENTOMINOES, |placements| {
+            solutions.push(placements.to_vec());
+            ControlFlow::Continue(())
+        });
+        let board = CellShape::rectangle(20, 3);
+        let equivalent = solutions.iter().filter(|a| solutions_equivalent(&board, a, &solutions[0])).count();
+        assert_eq!(equivalent, 4);
+    }
+
+    #[test]
+    fn all_rectangles_of_area() {
+        let results = solve_all_rectangles_of_area(60);
+        let sizes: Vec<(usize, usize)> = results.iter().map(|(size, _)| *size).collect();
+        assert_eq!(sizes, vec![(1, 60), (2, 30), (3, 20), (4, 15), (5, 12), (6, 10)]);
+        for ((width, height), solution) in &results[2..] {
+            let board = CellShape::rectangle(*width, *height);
+            assert_eq!(validate_solution(&board, solution.as_ref().unwrap()), Ok(()));
+        }
+        assert!(results[0].1.is_none() && results[1].1.is_none());
+
+        // 2x7 can't be split into pentominoes at all
+        let results = solve_all_rectangles_of_area(14);
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|(_, solution)| solution.is_none()));
+        // a 2x5 takes two pentominoes, but not two of the same
+        assert!(solve_all_rectangles_of_area(10).iter().all(|(_, solution)| solution.is_none()));
+    }
+
+    #[test]
+    fn greedy() {
+        let line = CellShape::rectangle(5, 1);
+        assert_eq!(validate_solution(&line, &greedy_cover(&line)), Ok(()));
+
+        for (width, height) in [(20, 3), (10, 6), (12, 5)] {
+            let board = CellShape::rectangle(width, height);
+            let placements = greedy_cover(&board);
+            match validate_solution(&board, &placements) {
+                Ok(()) => {}
+                // anything else wrong would mean the placements themselves are broken
+                Err(gap) => assert!(gap.ends_with("is not covered"), "{}", gap),
+            }
+        }
+    }
+
+    #[test]
+    fn csv() {
+        let mut out = Vec::new();
+        solutions_to_csv(20, 3, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines[0], "F,I,L,N,P,T,U,V,W,X,Y,Z");
+        // 2 solutions up to symmetry, each in 4 orientations
+        assert_eq!(lines.len(), 1 + 8);
+        for line in &lines[1..] {
+            let fields: Vec<&str> = line.split(',').collect();
+            assert_eq!(fields.len(), 12);
+            assert!(fields.iter().all(|f| f.split(' ').count() == 3));
+        }
+    }
+
+    #[test]
+    fn unused() {
+        assert_eq!(unused_pieces(&[]), PENTOMINOES.to_vec());
+        let placements: Vec<Placement> = [Pentomino::X, Pentomino::F, Pentomino::I]
+            .into_iter()
+            .map(|piece| Placement { piece, transform: Transform::identity(), offset: Vec2D::zero() })
+            .collect();
+        let unused = unused_pieces(&placements);
+        assert_eq!(unused.len(), 9);
+        assert!(!unused.contains(&Pentomino::X) && !unused.contains(&Pentomino::F) && !unused.contains(&Pentomino::I));
+
+        let solution = solve_region(&CellShape::rectangle(10, 6)).unwrap();
+        assert!(unused_pieces(&solution).is_empty());
+    }
+
+    #[test]
+    fn require_all_pieces() {
+        let board = CellShape::rectangle(5, 3);
+        let solution = solve_with_config(&board, &PENTOMINOES, &SolverConfig::default()).unwrap();
+        assert_eq!(validate_solution(&board, &solution), Ok(()));
+        assert_eq!(unused_pieces(&solution).len(), 9);
+
+        let all = SolverConfig {
+            require_all_pieces: true,
+            ..SolverConfig::default()
+        };
+        assert_eq!(solve_with_config(&board, &PENTOMINOES, &all), None);
+        let pieces: Vec<Pentomino> = solution.iter().map(|p| p.piece).collect();
+        let solution = solve_with_config(&board, &pieces, &all);
+        assert_eq!(solution.map(|s| s.len()), Some(3));
+        assert!(solve_with_config(&CellShape::rectangle(10, 6), &PENTOMINOES, &all).is_some());
+    }
+
+    #[test]
+    fn disjoint_pairs() {
+        let board = CellShape::rectangle(5, 2);
+        // one I in each row, in either order
+        assert_eq!(disjoint_placement_pairs(&board, Pentomino::I, Pentomino::I).len(), 2);
+        assert!(disjoint_placement_pairs(&board, Pentomino::X, Pentomino::I).is_empty());
+        // two pieces on a 2x5 cover all of it, so every pair is a tiling
+        let pairs = disjoint_placement_pairs(&board, Pentomino::L, Pentomino::L);
+        assert!(!pairs.is_empty());
+        for (a, b) in pairs {
+            let mut covered = a.cells();
+            covered.extend(b.cells());
+            assert_eq!(CellShape::from_coordinate_list(covered), board);
+        }
+
+        // 6 spots along a 1x10 line, and the two I's need to be 5 apart
+        let line = CellShape::rectangle(10, 1);
+        assert_eq!(disjoint_placement_pairs(&line, Pentomino::I, Pentomino::I).len(), 2);
+        let board = CellShape::rectangle(6, 2);
+        assert_eq!(disjoint_placement_pairs(&board, Pentomino::I, Pentomino::I).len(), 8);
+    }
+
+    #[test]
+    fn prefilled() {
+        let board = CellShape::rectangle(10, 6);
+        let solution = solve_region(&board).unwrap();
+        let completed = solve_with_prefilled(&board, &solution[..4]).unwrap();
+        assert_eq!(completed[..4], solution[..4]);
+        assert_eq!(validate_solution(&board, &completed), Ok(()));
+        assert_eq!(solve_with_prefilled(&board, &solution), Some(solution.clone()));
+
+        // the same piece twice, and a piece hanging off the board
+        assert_eq!(solve_with_prefilled(&board, &[solution[0], solution[0]]), None);
+        let off = Placement { offset: Vec2D::new(-1, 0), ..solution[0] };
+        assert_eq!(solve_with_prefilled(&board, &[off]), None);
+    }
+
+    #[test]
+    fn puzzle() {
+        let (board, givens) = generate_puzzle(10, 6, 5, 7).unwrap();
+        assert_eq!(board, CellShape::rectangle(10, 6));
+        assert_eq!(givens.len(), 5);
+        let solution = solve_with_prefilled(&board, &givens).unwrap();
+        assert_eq!(solution[..5], givens[..]);
+        assert_eq!(validate_solution(&board, &solution), Ok(()));
+
+        assert_eq!(generate_puzzle(10, 6, 5, 7), Some((board, givens)));
+        assert_eq!(generate_puzzle(20, 3, 20, 1).map(|(_, givens)| givens.len()), Some(12));
+        assert_eq!(generate_puzzle(7, 7, 3, 1), None);
+    }
+
+    #[test]
+    fn grid_of_pieces() {
+        let board = CellShape::rectangle(10, 6);
+        let solution = solve_region(&board).unwrap();
+        let grid = solution_to_grid(&board, &solution);
+        assert_eq!((grid.len(), grid[0].len()), (6, 10));
+        let mut counts = HashMap::new();
+        for label in grid.iter().flatten() {
+            *counts.entry(label.unwrap()).or_insert(0) += 1;
+        }
+        assert_eq!(counts.len(), 12);
+        assert!(counts.values().all(|&n| n == 5));
+        for p in &solution {
+            assert!(p.cells().iter().all(|c| grid[c.y as usize][c.x as usize] == Some(p.piece)));
+        }
+
+        // uncovered tiles stay empty
+        let grid = solution_to_grid(&board, &solution[..1]);
+        assert_eq!(grid.iter().flatten().filter(|label| label.is_some()).count(), 5);
+    }
+
+    #[test]
+    fn forced_corner() {
+        let board = CellShape::rectangle(10, 6);
+        let mut count = 0;
+        for solution in boards_with_forced_corner(10, 6, Pentomino::V) {
+            assert_eq!(validate_solution(&board, &solution), Ok(()));
+            assert_eq!(solution[0].piece, Pentomino::V);
+            assert!(solution[0].cells().contains(&Coord::origin()));
+            count += 1;
+        }
+        assert!(count > 0);
+        assert_eq!(boards_with_forced_corner(10, 6, Pentomino::X).count(), 0);
+
+        let corners = corner_piece_distribution(20, 3);
+        for p in PENTOMINOES {
+            let expected = corners.get(&p).copied().unwrap_or(0);
+            assert_eq!(boards_with_forced_corner(20, 3, p).count() as u64, expected, "{:?}", p);
+        }
+    }
+
+    #[test]
+    fn deadline() {
+        let past = SolverConfig {
+            deadline: Some(Instant::now()),
+            ..SolverConfig::default()
+        };
+        let (solution, stats) = solve_with_stats(&CellShape::rectangle(10, 6), &PENTOMINOES, &past);
+        assert_eq!((solution, stats.nodes), (None, 0));
+        assert!(stats.timed_out);
+
+        let later = SolverConfig {
+            deadline: Some(Instant::now() + std::time::Duration::from_secs(600)),
+            ..SolverConfig::default()
+        };
+        let (solution, stats) = solve_with_stats(&CellShape::rectangle(10, 6), &PENTOMINOES, &later);
+        assert!(solution.is_some() && !stats.timed_out);
+        // a board with no solution isn't reported as timed out, however close the deadline is
+        let stats = for_each_solution_with_config(&CellShape::rectangle(7, 7), &PENTOMINOES, &later, |_| ControlFlow::Continue(()));
+        assert_eq!((stats.solutions, stats.timed_out), (0, false));
+    }
+
+    #[test]
+    fn holes() {
+        // a 2 tile pocket at the end of a line, or one on each side
+        let line = CellShape::rectangle(7, 1);
+        let i = Placement { piece: Pentomino::I, transform: Transform::rotate90(), offset: Vec2D::zero() };
+        assert_eq!(holes_after_placement(&line, &i), 1);
+        let middle = Placement { offset: Vec2D::new(1, 0), ..i };
+        assert_eq!(holes_after_placement(&line, &middle), 2);
+        assert_eq!(holes_after_placement(&CellShape::rectangle(10, 1), &i), 0);
+
+        // a V in the corner of a 3x3 leaves a 2x2 square
+        let v = Placement { piece: Pentomino::V, transform: Transform::identity(), offset: Vec2D::zero() };
+        assert_eq!(holes_after_placement(&CellShape::rectangle(3, 3), &v), 1);
+        assert_eq!(holes_after_placement(&CellShape::rectangle(10, 6), &v), 0);
+    }
+
+    #[test]
+    fn framed() {
+        // an I lying along the top edge and an L around the bottom left corner
+        let top = Placement { piece: Pentomino::I, transform: Transform::rotate90(), offset: Vec2D::zero() };
+        let side = Placement { piece: Pentomino::L, transform: Transform::identity(), offset: Vec2D::new(0, 2) };
+        let solution = solve_framed(10, 6, &[top, side]).unwrap().unwrap();
+        assert_eq!(solution[..2], [top, side]);
+        assert_eq!(validate_solution(&CellShape::rectangle(10, 6), &solution), Ok(()));
+
+        let inside = Placement { offset: Vec2D::new(1, 1), ..top };
+        assert!(solve_framed(10, 6, &[inside]).is_err());
+        assert_eq!(solve_framed(10, 6, &[top, top]), Ok(None));
+    }
+}
@@ -0,0 +1,69 @@
+use std::fmt::Write;
+
+use crate::pentomino::PENTOMINOES;
+
+// Side length of a tile, in pixels
+const TILE: usize = 16;
+// Every orientation fits in a 5 by 5 slot, with a tile of space around it
+const SLOT: usize = 6 * TILE;
+// Room on the left of each row for the piece's letter
+const LABEL_WIDTH: usize = 2 * TILE;
+
+/// Draws every orientation of every pentomino as one SVG document,
+/// one labeled row per pentomino with its tiles filled in its color.
+pub fn orientation_sheet_svg() -> String {
+    let rows: Vec<_> = PENTOMINOES.iter().map(|&p| (p, p.shapes_sorted())).collect();
+    let columns = rows.iter().map(|(_, shapes)| shapes.len()).max().unwrap_or(0);
+    let width = LABEL_WIDTH + columns * SLOT;
+    let height = rows.len() * SLOT;
+
+    let mut svg = String::new();
+    let _ = writeln!(svg, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#);
+    for (row, (piece, shapes)) in rows.iter().enumerate() {
+        let top = row * SLOT;
+        let _ = writeln!(
+            svg,
+            r#"  <text x="{}" y="{}" font-family="monospace" font-size="{}" text-anchor="middle">{}</text>"#,
+            LABEL_WIDTH / 2,
+            top + SLOT / 2 + TILE / 2,
+            TILE * 3 / 2,
+            piece.letter()
+        );
+        for (column, shape) in shapes.iter().enumerate() {
+            let left = LABEL_WIDTH + column * SLOT + TILE / 2;
+            for c in shape.filled_tiles() {
+                let _ = writeln!(
+                    svg,
+                    r#"  <rect x="{}" y="{}" width="{TILE}" height="{TILE}" fill="{}" stroke="black"/>"#,
+                    left + c.x as usize * TILE,
+                    top + TILE / 2 + c.y as usize * TILE,
+                    piece.color()
+                );
+            }
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sheet_has_every_orientation() {
+        let svg = orientation_sheet_svg();
+        assert!(svg.starts_with("<svg ") && svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<text ").count(), 12);
+        for p in PENTOMINOES {
+            assert!(svg.contains(&format!(">{}</text>", p.letter())));
+        }
+        let tiles: usize = PENTOMINOES
+            .iter()
+            .flat_map(|p| p.shapes())
+            .map(|shape| shape.size())
+            .sum();
+        assert_eq!(tiles, 63 * 5);
+        assert_eq!(svg.matches("<rect ").count(), tiles);
+    }
+}
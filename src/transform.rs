@@ -18,6 +18,26 @@ pub const RIGID_SYMMETRIES: [Transform; 8] = [
     Transform::rotate270(),
 ];
 
+// The constructor names of RIGID_SYMMETRIES, in the same order
+const RIGID_SYMMETRY_NAMES: [&str; 8] = [
+    "identity",
+    "mirror_horizontal",
+    "mirror_vertical",
+    "mirror_diagonal",
+    "mirror_diagonal2",
+    "rotate90",
+    "rotate180",
+    "rotate270",
+];
+
+// The 4 rotations, which keep a piece the same side up
+pub const ROTATIONS: [Transform; 4] = [
+    Transform::identity(),
+    Transform::rotate90(),
+    Transform::rotate180(),
+    Transform::rotate270(),
+];
+
 impl Transform {
     /// Preserves original tile grid
     pub const fn identity() -> Transform {
@@ -103,6 +123,43 @@ impl Transform {
     }
 
 
+    /// Moves every coordinate by `dx` and `dy`
+    pub const fn translate(dx: isize, dy: isize) -> Transform {
+        Transform { elems: [
+            [1, 0, dx],
+            [0, 1, dy],
+            [0, 0, 1],
+        ]}
+    }
+    /// Turns counter clockwise by `quarter_turns` quarters about `center` instead of the origin,
+    /// so `center` itself stays put. Negative turns go clockwise
+    pub const fn rotate_about(center: Coord, quarter_turns: i32) -> Transform {
+        let rotation = match quarter_turns.rem_euclid(4) {
+            0 => Transform::identity(),
+            1 => Transform::rotate90(),
+            2 => Transform::rotate180(),
+            _ => Transform::rotate270(),
+        };
+        Transform::builder()
+            .translate(-center.x, -center.y)
+            .then(rotation)
+            .translate(center.x, center.y)
+            .build()
+    }
+    /// Starts composing a transform one step at a time
+    pub const fn builder() -> TransformBuilder {
+        TransformBuilder { transform: Transform::identity() }
+    }
+
+    /// The name of the constructor giving this transform, e.g. `"rotate90"`.
+    /// Transforms outside RIGID_SYMMETRIES, such as translations, are `"unknown"`.
+    pub fn name(self) -> &'static str {
+        RIGID_SYMMETRIES
+            .iter()
+            .position(|&t| t == self)
+            .map_or("unknown", |i| RIGID_SYMMETRY_NAMES[i])
+    }
+
     /// Apply the transform to a coordinate point
     pub const fn transform_coord(self, coord: Coord) -> Coord {
         let coord = Coord {
@@ -120,6 +177,13 @@ impl Transform {
         CellShape::from_coordinate_list(coords)
     }
 
+    /// Transforms the shape, also returning the `(width, height)` of the result
+    pub fn transform_shape_with_bbox(self, cell_shape: CellShape) -> (CellShape, (isize, isize)) {
+        let shape = self.transform_shape(cell_shape);
+        let bbox = shape.bounding_box();
+        (shape, bbox)
+    }
+
 
     const fn at(self, i: usize, j: usize) -> isize {
         self.elems[j][i]
@@ -144,7 +208,56 @@ impl Transform {
 }
 
 
-impl std::ops::Mul for Transform {
+/// Composes a transform from named steps, in the order they are applied.
+/// `Transform::builder().rotate90().translate(1, 0).build()` rotates first, then translates.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TransformBuilder {
+    transform: Transform,
+}
+
+impl TransformBuilder {
+    /// Applies `step` after the steps so far
+    pub const fn then(self, step: Transform) -> TransformBuilder {
+        TransformBuilder { transform: self.transform.compose(step) }
+    }
+    pub const fn rotate90(self) -> TransformBuilder {
+        self.then(Transform::rotate90())
+    }
+    pub const fn rotate180(self) -> TransformBuilder {
+        self.then(Transform::rotate180())
+    }
+    pub const fn rotate270(self) -> TransformBuilder {
+        self.then(Transform::rotate270())
+    }
+    pub const fn mirror_horizontal(self) -> TransformBuilder {
+        self.then(Transform::mirror_horizontal())
+    }
+    pub const fn mirror_vertical(self) -> TransformBuilder {
+        self.then(Transform::mirror_vertical())
+    }
+    pub const fn translate(self, dx: isize, dy: isize) -> TransformBuilder {
+        self.then(Transform::translate(dx, dy))
+    }
+    pub const fn build(self) -> Transform {
+        self.transform
+    }
+}
+
+/// Entry `[i][j]` is the index in RIGID_SYMMETRIES of `RIGID_SYMMETRIES[i] * RIGID_SYMMETRIES[j]`
+pub fn multiplication_table() -> [[usize; 8]; 8] {
+    let mut table = [[0; 8]; 8];
+    for (i, &lhs) in RIGID_SYMMETRIES.iter().enumerate() {
+        for (j, &rhs) in RIGID_SYMMETRIES.iter().enumerate() {
+            table[i][j] = RIGID_SYMMETRIES
+                .iter()
+                .position(|&t| t == lhs * rhs)
+                .expect("the rigid symmetries are closed under composition");
+        }
+    }
+    table
+}
+
+impl core::ops::Mul for Transform {
     type Output = Transform;
 
     fn mul(self, rhs: Self) -> Self::Output {
@@ -342,6 +455,28 @@ mod tests {
     //    ROTATIONS
     // ======================
 
+    #[test]
+    fn rotate90_transform_shape_with_bbox() {
+        use crate::cell_shape::Tile::{
+            Empty as e,
+            Filled as F
+        };
+        let t = Transform::rotate90();
+
+        let before = CellShape::from_2darray([
+            [F, e],
+            [F, e],
+            [F, e],
+            [F, F],
+        ]);
+        assert_eq!(before.bounding_box(), (2, 4));
+
+        let (after, bbox) = t.transform_shape_with_bbox(before);
+
+        assert_eq!(bbox, (4, 2));
+        assert_eq!(after.bounding_box(), bbox);
+    }
+
     #[test]
     fn rotate90_transform_coord() {
         let t = Transform::rotate90();
@@ -487,4 +622,45 @@ mod tests {
     fn identity_is_hh() {
         assert_eq!(Transform::identity(), H * H);
     }
+
+    #[test]
+    fn multiplication_table_is_latin_square() {
+        let table = multiplication_table();
+        for i in 0..8 {
+            let mut row: Vec<usize> = table[i].to_vec();
+            let mut column: Vec<usize> = table.iter().map(|row| row[i]).collect();
+            row.sort();
+            column.sort();
+            assert_eq!(row, (0..8).collect::<Vec<_>>());
+            assert_eq!(column, (0..8).collect::<Vec<_>>());
+        }
+        assert_eq!(table[0], [0, 1, 2, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn builder_applies_steps_in_order() {
+        let c = Coord::new(2, 5);
+        let built = Transform::builder().rotate90().translate(1, 0).build();
+        let manual = Transform::translate(1, 0).transform_coord(Transform::rotate90().transform_coord(c));
+        assert_eq!(built.transform_coord(c), manual);
+        assert_eq!(manual, Coord::new(6, -2));
+        assert_ne!(built, Transform::builder().translate(1, 0).rotate90().build());
+        assert_eq!(Transform::builder().build(), Transform::identity());
+    }
+
+    #[test]
+    fn rotate_about_center() {
+        let f = crate::pentomino::Pentomino::F.representative();
+        let center = Coord::new(1, 1);
+        let t = Transform::rotate_about(center, 1);
+        assert_eq!(t.transform_coord(center), center);
+        let turned: Vec<Coord> = f.filled_tiles().map(|c| t.transform_coord(c)).collect();
+        // the 3x3 box turns onto itself, so nothing needs to be shifted back
+        assert!(turned.iter().all(|c| (0..3).contains(&c.x) && (0..3).contains(&c.y)));
+        assert_eq!(CellShape::from_coordinate_list(turned), Transform::rotate90().transform_shape(f.clone()));
+
+        assert_eq!(Transform::rotate_about(center, 4), Transform::identity());
+        assert_eq!(Transform::rotate_about(center, -1), Transform::rotate_about(center, 3));
+        assert_eq!(Transform::rotate_about(Coord::origin(), 2), Transform::rotate180());
+    }
 }
@@ -0,0 +1,62 @@
+#[cfg(feature = "std")]
+use std::collections::HashSet;
+
+#[cfg(feature = "std")]
+use crate::cell_shape::{Tile, CellShape};
+#[cfg(feature = "std")]
+use crate::pentomino::create_all_orientations;
+#[cfg(feature = "std")]
+use crate::transform::RIGID_SYMMETRIES;
+
+/// The two pieces made of three tiles, which tile small boards quickly
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Tromino {
+    I,
+    L,
+}
+
+pub const TROMINOES: [Tromino; 2] = [
+    Tromino::I,
+    Tromino::L,
+];
+
+impl Tromino {
+    /// Returns all possible orientations for this tromino
+    #[cfg(feature = "std")]
+    pub fn shapes(self) -> HashSet<CellShape> {
+        create_all_orientations(self.representative(), RIGID_SYMMETRIES.as_slice())
+    }
+    /// The orientation the other orientations are generated from
+    #[cfg(feature = "std")]
+    pub(crate) fn representative(self) -> CellShape {
+        use Tile::{
+            Empty as o,
+            Filled as F,
+        };
+        match self {
+            Tromino::I => CellShape::from_2darray([
+                [F],
+                [F],
+                [F],
+            ]),
+            Tromino::L => CellShape::from_2darray([
+                [F, o],
+                [F, F],
+            ]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orientation_counts() {
+        assert_eq!(Tromino::I.shapes().len(), 2);
+        assert_eq!(Tromino::L.shapes().len(), 4);
+        for t in TROMINOES {
+            assert!(t.shapes().iter().all(|s| s.size() == 3));
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use std::process::Command;
+
+// The coordinate, transform and shape core has to build without std for embedded users.
+// CI checks this with its own `cargo build --lib --no-default-features` step. The nested build is slow
+// and needs the dependencies cached offline, so run it locally with `cargo test --test no_std -- --ignored`
+#[test]
+#[ignore]
+fn core_builds_without_std() {
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let output = Command::new(env!("CARGO"))
+        .args(["build", "--lib", "--offline", "--no-default-features"])
+        // a separate target directory, so this doesn't wait on the lock held by the running tests
+        .args(["--target-dir", &format!("{manifest_dir}/target/no_std")])
+        .current_dir(manifest_dir)
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -0,0 +1,52 @@
+use std::process::Command;
+
+// Runs the solve command, returning its stdout
+fn solve(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_pentominoes"))
+        .arg("solve")
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn all_with_limit() {
+    let out = solve(&["20", "3", "--all", "--limit", "3"]);
+    let boards: Vec<&str> = out.split("\n\n").collect();
+    assert_eq!(boards.len(), 3, "{out}");
+    assert!(boards.iter().all(|board| board.trim_end().lines().count() == 3));
+}
+
+#[test]
+fn all_without_limit() {
+    // the 8 solutions are the 2 distinct ones, reflected and rotated
+    let out = solve(&["20", "3", "--all"]);
+    assert_eq!(out.split("\n\n").count(), 8);
+    let json = solve(&["20", "3", "--all", "--json"]);
+    assert_eq!(json.lines().count(), 8);
+}
+
+#[test]
+fn zero_timeout() {
+    let start = std::time::Instant::now();
+    assert_eq!(solve(&["10", "6", "--timeout", "0"]), "timed out\n");
+    assert_eq!(solve(&["10", "6", "--all", "--timeout", "0"]), "");
+    // a full enumeration of 6x10 takes seconds
+    assert!(start.elapsed().as_secs() < 2);
+    assert_eq!(solve(&["20", "3", "--all", "--json", "--timeout", "600"]).lines().count(), 8);
+}
+
+#[test]
+fn invalid_timeout() {
+    for timeout in ["-1", "inf", "NaN", "soon"] {
+        let output = Command::new(env!("CARGO_BIN_EXE_pentominoes"))
+            .args(["solve", "10", "6", &format!("--timeout={timeout}")])
+            .output()
+            .unwrap();
+        // clap reports usage errors with exit code 2, a panic would be 101
+        assert_eq!(output.status.code(), Some(2), "{timeout}");
+        assert!(String::from_utf8_lossy(&output.stderr).contains("seconds"), "{timeout}");
+    }
+}
@@ -0,0 +1,45 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+const SOLUTION: &str = "\
+UUXIIIIINNNFTWYYYYZV
+UXXXPPLNNFFFTWWYZZZV
+UUXPPPLLLLFTTTWWZVVV
+";
+
+// Writes the contents to a file in the temp directory, named for the test using it
+fn write_temp(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("pentominoes-verify-{}-{name}", std::process::id()));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+fn verify(board: &str, solution: &str, name: &str) -> std::process::Output {
+    let board = write_temp(&format!("{name}-board"), board);
+    let solution = write_temp(&format!("{name}-solution"), solution);
+    let output = Command::new(env!("CARGO_BIN_EXE_pentominoes"))
+        .arg("verify")
+        .arg(&board)
+        .arg(&solution)
+        .output()
+        .unwrap();
+    let _ = std::fs::remove_file(board);
+    let _ = std::fs::remove_file(solution);
+    output
+}
+
+#[test]
+fn valid_solution() {
+    let board = "#".repeat(20) + "\n";
+    let output = verify(&board.repeat(3), SOLUTION, "valid");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+}
+
+#[test]
+fn invalid_solution() {
+    // the board is one row short, so the last row of the solution hangs off of it
+    let board = "#".repeat(20) + "\n";
+    let output = verify(&board.repeat(2), SOLUTION, "invalid");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("outside the board"));
+}